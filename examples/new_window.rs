@@ -89,7 +89,7 @@ fn main() -> io::Result<()> {
         icon: Some(icon),
         ..Default::default()
     };
-    let class: WindowClass<MyListener> = WindowClass::register_new("myclass1", appearance)?;
+    let class: WindowClass<MyListener> = WindowClass::register_new("myclass1", appearance, None)?;
     let window = Window::create_new(&class, &listener, "mywindow1")?;
     let notification_icon_options = NotificationIconOptions {
         icon: Some(icon),
@@ -150,7 +150,7 @@ fn main() -> io::Result<()> {
                 }
                 MyMessage::MenuItem(MenuID::ShowMessageBox) => {
                     show_message_box(
-                        window_handle,
+                        Some(window_handle),
                         MessageBoxOptions {
                             message: Some("Message"),
                             caption: Some("Caption"),
@@ -1,89 +1,317 @@
 //! Clipboard access.
 
-use std::ffi::OsString;
 use std::io;
-use std::os::windows::ffi::OsStringExt;
+use std::marker::PhantomData;
+use std::mem;
 use std::path::PathBuf;
 
-use windows::Win32::Foundation::HGLOBAL;
+use num_enum::{
+    FromPrimitive,
+    IntoPrimitive,
+};
+#[cfg(feature = "ui")]
+use windows::Win32::Foundation::HWND;
+use windows::Win32::Foundation::{
+    GetLastError,
+    HGLOBAL,
+    NO_ERROR,
+};
 use windows::Win32::System::DataExchange::{
     CloseClipboard,
+    EnumClipboardFormats,
     GetClipboardData,
+    GetClipboardFormatNameW,
+    GetClipboardSequenceNumber,
     OpenClipboard,
 };
-use windows::Win32::System::Ole::CF_HDROP;
-use windows::Win32::UI::Shell::{
-    DragQueryFileW,
-    HDROP,
+use windows::Win32::System::Ole::{
+    CF_BITMAP,
+    CF_DIB,
+    CF_DIBV5,
+    CF_DIF,
+    CF_ENHMETAFILE,
+    CF_HDROP,
+    CF_LOCALE,
+    CF_METAFILEPICT,
+    CF_OEMTEXT,
+    CF_PALETTE,
+    CF_PENDATA,
+    CF_RIFF,
+    CF_SYLK,
+    CF_TEXT,
+    CF_TIFF,
+    CF_UNICODETEXT,
+    CF_WAVE,
 };
+use windows::Win32::UI::Shell::HDROP;
 
 use crate::internal::{
+    custom_err_with_code,
+    get_hdrop_file_list,
     GlobalLockedData,
-    ReturnValue,
 };
+use crate::string::FromWideString;
+#[cfg(feature = "ui")]
+use crate::ui::WindowHandle;
 
-/// Returns a list of file paths that have been copied to the clipboard.
+/// Exclusive access to the clipboard, allowing multiple operations without other applications
+/// modifying the clipboard in between.
 ///
-/// Will return `Err` if the clipboard cannot be accessed or does not contain files.
-pub fn get_file_list() -> io::Result<Vec<PathBuf>> {
-    let f = || {
+/// The clipboard is closed again on drop. Functions in this module that don't require an instance
+/// open and close the clipboard themselves.
+///
+/// The clipboard is opened for the current thread only, which is why this type is not [`Send`].
+#[derive(Debug)]
+pub struct ClipboardAccess {
+    phantom: PhantomData<*mut ()>,
+}
+
+impl ClipboardAccess {
+    /// Opens the clipboard without associating it with a window.
+    ///
+    /// Will return `Err` if another thread or application currently has the clipboard open.
+    pub fn open() -> io::Result<Self> {
+        unsafe {
+            OpenClipboard(None)?;
+        }
+        Ok(Self {
+            phantom: PhantomData,
+        })
+    }
+
+    /// Opens the clipboard, associating it with the given window.
+    #[cfg(feature = "ui")]
+    pub fn open_for_window(window: &WindowHandle) -> io::Result<Self> {
+        unsafe {
+            OpenClipboard(HWND::from(window))?;
+        }
+        Ok(Self {
+            phantom: PhantomData,
+        })
+    }
+
+    /// Closes the clipboard, returning any error.
+    ///
+    /// Dropping the instance also closes the clipboard, but ignores errors.
+    pub fn close(self) -> io::Result<()> {
+        mem::forget(self);
+        unsafe { CloseClipboard()? };
+        Ok(())
+    }
+
+    /// Returns a list of file paths that have been copied to the clipboard.
+    ///
+    /// Will return `Err` if the clipboard does not contain files.
+    pub fn get_file_list(&self) -> io::Result<Vec<PathBuf>> {
         let mut clipboard_data = {
             let clipboard_data = unsafe { GetClipboardData(CF_HDROP.0.into()) }?;
             GlobalLockedData::lock(HGLOBAL(clipboard_data.0 as *mut _))?
         };
 
-        let num_files = unsafe { DragQueryFileW(HDROP(clipboard_data.ptr()), u32::MAX, None) };
-        let file_names: io::Result<Vec<PathBuf>> = (0..num_files)
-            .map(|file_index| {
-                let required_size =
-                    unsafe { 1 + DragQueryFileW(HDROP(clipboard_data.ptr()), file_index, None) }
-                        .if_null_to_error(|| io::ErrorKind::Other.into())?;
-                let file_str_buf = {
-                    let mut buffer = vec![0; required_size as usize];
-                    unsafe {
-                        DragQueryFileW(
-                            HDROP(clipboard_data.ptr()),
-                            file_index,
-                            Some(buffer.as_mut_slice()),
-                        )
-                    }
-                    .if_null_to_error(|| io::ErrorKind::Other.into())?;
-                    // Set length, remove terminating zero
-                    buffer.truncate(buffer.len() - 1);
-                    buffer
-                };
-                let os_string = OsString::from_wide(&file_str_buf);
-                Ok(PathBuf::from(os_string))
-            })
-            .collect();
-        file_names
-    };
-    with_open_clipboard_do(f)
-}
+        get_hdrop_file_list(HDROP(clipboard_data.ptr()))
+    }
 
-fn with_open_clipboard_do<F, R>(f: F) -> io::Result<R>
-where
-    F: FnOnce() -> io::Result<R>,
-{
-    unsafe {
-        OpenClipboard(None)?;
+    /// Returns the formats of the data currently stored in the clipboard.
+    ///
+    /// The formats are returned in the order in which they were placed on the clipboard,
+    /// which is usually the preferred order.
+    pub fn available_formats(&self) -> io::Result<Vec<ClipboardFormat>> {
+        let mut result = Vec::new();
+        let mut raw_format = 0;
+        loop {
+            raw_format = unsafe { EnumClipboardFormats(raw_format) };
+            if raw_format == 0 {
+                let last_error = unsafe { GetLastError() };
+                if last_error != NO_ERROR {
+                    return Err(custom_err_with_code(
+                        "Cannot enumerate clipboard formats",
+                        last_error.0,
+                    ));
+                }
+                break;
+            }
+            result.push(ClipboardFormat::from(raw_format));
+        }
+        Ok(result)
     }
-    let result = f();
-    unsafe {
-        CloseClipboard()?;
+}
+
+impl Drop for ClipboardAccess {
+    /// Closes the clipboard, ignoring errors.
+    ///
+    /// Use [`ClipboardAccess::close`] to handle errors.
+    fn drop(&mut self) {
+        let _ = unsafe { CloseClipboard() };
     }
+}
+
+/// Returns a list of file paths that have been copied to the clipboard.
+///
+/// Will return `Err` if the clipboard cannot be accessed or does not contain files.
+///
+/// See also [`ClipboardAccess::get_file_list`].
+pub fn get_file_list() -> io::Result<Vec<PathBuf>> {
+    let clipboard = ClipboardAccess::open()?;
+    let result = clipboard.get_file_list();
+    clipboard.close()?;
+    result
+}
+
+/// Returns the formats of the data currently stored in the clipboard.
+///
+/// See also [`ClipboardAccess::available_formats`].
+pub fn available_formats() -> io::Result<Vec<ClipboardFormat>> {
+    let clipboard = ClipboardAccess::open()?;
+    let result = clipboard.available_formats();
+    clipboard.close()?;
     result
 }
 
+/// Returns the clipboard sequence number, which changes whenever the clipboard contents change.
+///
+/// This can be used to cheaply detect clipboard changes by polling, without opening the clipboard.
+/// Returns 0 if the current window station has no clipboard access.
+pub fn get_sequence_number() -> u32 {
+    unsafe { GetClipboardSequenceNumber() }
+}
+
+/// A clipboard data format.
+///
+/// Formats registered by applications are represented by [`ClipboardFormat::Other`],
+/// their names can be retrieved using [`ClipboardFormat::get_registered_name`].
+#[derive(IntoPrimitive, FromPrimitive, Copy, Clone, Eq, PartialEq, Hash, Debug)]
+#[repr(u32)]
+pub enum ClipboardFormat {
+    Text = CF_TEXT.0 as u32,
+    Bitmap = CF_BITMAP.0 as u32,
+    MetafilePicture = CF_METAFILEPICT.0 as u32,
+    SymbolicLink = CF_SYLK.0 as u32,
+    DataInterchange = CF_DIF.0 as u32,
+    Tiff = CF_TIFF.0 as u32,
+    OemText = CF_OEMTEXT.0 as u32,
+    DeviceIndependentBitmap = CF_DIB.0 as u32,
+    Palette = CF_PALETTE.0 as u32,
+    PenData = CF_PENDATA.0 as u32,
+    Riff = CF_RIFF.0 as u32,
+    Wave = CF_WAVE.0 as u32,
+    UnicodeText = CF_UNICODETEXT.0 as u32,
+    EnhancedMetafile = CF_ENHMETAFILE.0 as u32,
+    /// A list of files, see [`get_file_list`].
+    FileList = CF_HDROP.0 as u32,
+    Locale = CF_LOCALE.0 as u32,
+    DeviceIndependentBitmapV5 = CF_DIBV5.0 as u32,
+    #[num_enum(catch_all)]
+    Other(u32),
+}
+
+impl ClipboardFormat {
+    /// Returns the name of an application-registered format.
+    ///
+    /// Returns `None` for standard and private formats, which have no registered name.
+    pub fn get_registered_name(&self) -> Option<String> {
+        const MAX_NAME_CHARS: usize = 256;
+        match self {
+            Self::Other(raw_format) => {
+                let mut buffer: Vec<u16> = vec![0; MAX_NAME_CHARS];
+                let copied_chars = unsafe { GetClipboardFormatNameW(*raw_format, &mut buffer) };
+                if copied_chars <= 0 {
+                    return None;
+                }
+                buffer.truncate(copied_chars as usize);
+                Some(buffer.to_string_lossy())
+            }
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::*;
+    use std::sync::{
+        Mutex,
+        MutexGuard,
+        PoisonError,
+    };
     use std::time::Duration;
 
+    #[cfg(feature = "ui")]
+    use windows::core::PCWSTR;
+    #[cfg(feature = "ui")]
+    use windows::Win32::Foundation::HANDLE;
+    #[cfg(feature = "ui")]
+    use windows::Win32::System::DataExchange::{
+        EmptyClipboard,
+        RegisterClipboardFormatW,
+        SetClipboardData,
+    };
+
+    use super::*;
+    #[cfg(feature = "ui")]
+    use crate::string::ToWideString;
+
+    /// Opening the clipboard fails while another thread has it open, so tests must not access it concurrently.
+    static CLIPBOARD_LOCK: Mutex<()> = Mutex::new(());
+
+    fn lock_clipboard() -> MutexGuard<'static, ()> {
+        CLIPBOARD_LOCK
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[cfg(feature = "ui")]
+    const TEST_FORMAT_NAME: &str = "winapi_easy_test_format";
+
+    /// Replaces the clipboard contents with a registered test format owned by the window.
+    #[cfg(feature = "ui")]
+    fn set_test_format(window: &WindowHandle) -> io::Result<ClipboardFormat> {
+        let raw_format = unsafe {
+            RegisterClipboardFormatW(PCWSTR::from_raw(TEST_FORMAT_NAME.to_wide_string().as_ptr()))
+        };
+        if raw_format == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let clipboard = ClipboardAccess::open_for_window(window)?;
+        unsafe {
+            EmptyClipboard()?;
+            // Using delayed rendering, since the data itself is never requested
+            SetClipboardData(raw_format, HANDLE::default())?;
+        }
+        clipboard.close()?;
+        Ok(ClipboardFormat::from(raw_format))
+    }
+
     #[test]
     fn open_clipboard() -> io::Result<()> {
-        with_open_clipboard_do(|| {
-            std::thread::sleep(Duration::from_millis(0));
+        let _lock = lock_clipboard();
+        let clipboard = ClipboardAccess::open()?;
+        std::thread::sleep(Duration::from_millis(0));
+        clipboard.close()?;
+        Ok(())
+    }
+
+    #[cfg(feature = "ui")]
+    #[test]
+    fn check_available_formats() -> io::Result<()> {
+        let _lock = lock_clipboard();
+        crate::ui::tests::with_test_window(|window| {
+            let format = set_test_format(window.as_ref())?;
+            assert!(available_formats()?.contains(&format));
+            assert_eq!(
+                format.get_registered_name().as_deref(),
+                Some(TEST_FORMAT_NAME)
+            );
+            Ok(())
+        })
+    }
+
+    #[cfg(feature = "ui")]
+    #[test]
+    fn check_sequence_number() -> io::Result<()> {
+        let _lock = lock_clipboard();
+        crate::ui::tests::with_test_window(|window| {
+            let sequence_number = get_sequence_number();
+            set_test_format(window.as_ref())?;
+            assert_ne!(get_sequence_number(), sequence_number);
             Ok(())
         })
     }
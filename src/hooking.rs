@@ -8,6 +8,7 @@ use windows::Win32::Foundation::{
     POINT,
     WPARAM,
 };
+use windows::Win32::System::SystemInformation::GetTickCount;
 use windows::Win32::UI::WindowsAndMessaging::{
     CallNextHookEx,
     SetWindowsHookExW,
@@ -20,20 +21,31 @@ use windows::Win32::UI::WindowsAndMessaging::{
     WINDOWS_HOOK_ID,
     WM_KEYDOWN,
     WM_KEYUP,
+    WM_LBUTTONDBLCLK,
     WM_LBUTTONDOWN,
     WM_LBUTTONUP,
+    WM_MBUTTONDBLCLK,
     WM_MBUTTONDOWN,
     WM_MBUTTONUP,
+    WM_MOUSEHWHEEL,
     WM_MOUSEMOVE,
     WM_MOUSEWHEEL,
+    WM_RBUTTONDBLCLK,
     WM_RBUTTONDOWN,
     WM_RBUTTONUP,
     WM_SYSKEYDOWN,
     WM_SYSKEYUP,
+    WM_XBUTTONDBLCLK,
     WM_XBUTTONDOWN,
     WM_XBUTTONUP,
 };
 
+#[cfg(all(feature = "process", feature = "ui"))]
+use windows::Win32::UI::WindowsAndMessaging::{
+    CWPSTRUCT,
+    WH_CALLWNDPROC,
+};
+
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fmt::Debug;
@@ -43,15 +55,24 @@ use std::sync::{
     Mutex,
     OnceLock,
 };
+use std::time::{
+    Duration,
+    Instant,
+};
 
 use crate::input::{
     KeyboardKey,
     MouseButton,
+    MouseHScrollEvent,
     MouseScrollEvent,
 };
 use crate::internal::catch_unwind_and_abort;
 use crate::internal::windows_missing::HIWORD;
 use crate::messaging::ThreadMessageLoop;
+#[cfg(all(feature = "process", feature = "ui"))]
+use crate::process::ThreadId;
+#[cfg(all(feature = "process", feature = "ui"))]
+use crate::ui::WindowHandle;
 
 use private::*;
 
@@ -65,7 +86,7 @@ pub trait LowLevelInputHook: HookType + Copy {
         F: FnMut(Self::Message) -> HookReturnValue + Send,
     {
         // Always using ID 0 only works with ThreadLocalRawClosureStore
-        let _handle = Self::add_hook::<0, _>(user_callback)?;
+        let _handle = Self::add_hook::<0, _>(0, user_callback)?;
         ThreadMessageLoop::run_thread_message_loop(|| Ok(()))?;
         Ok(())
     }
@@ -95,12 +116,91 @@ impl HookType for LowLevelKeyboardHook {
 
 impl LowLevelInputHook for LowLevelKeyboardHook {}
 
+/// A hook observing the messages sent to the window procedures of a thread's windows.
+///
+/// Only the messages sent with `SendMessage` and similar functions are reported, not the posted ones.
+/// The messages cannot be modified or blocked.
+#[cfg(all(feature = "process", feature = "ui"))]
+#[derive(Copy, Clone, Debug)]
+pub enum CallWndProcHook {}
+
+#[cfg(all(feature = "process", feature = "ui"))]
+impl CallWndProcHook {
+    /// Installs the hook for the given thread and runs a thread message loop until a quit message is received.
+    ///
+    /// The thread must belong to the current process, e.g. as returned by
+    /// [`WindowHandle::get_creator_thread_id`] for a window of this process. The callback will be called
+    /// from that thread.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if another [`CallWndProcHook`] is already running in this process.
+    pub fn run_hook<F>(thread_id: ThreadId, user_callback: &mut F) -> io::Result<()>
+    where
+        F: FnMut(CallWndProcMessage) + Send,
+    {
+        let mut callback = |message| {
+            user_callback(message);
+            HookReturnValue::CallNextHook
+        };
+        // Always using ID 0 only works for a single hook with GlobalRawClosureStore
+        let _handle = Self::add_hook::<0, _>(thread_id.0, &mut callback)?;
+        ThreadMessageLoop::run_thread_message_loop(|| Ok(()))?;
+        Ok(())
+    }
+}
+
+#[cfg(all(feature = "process", feature = "ui"))]
+impl HookType for CallWndProcHook {
+    const TYPE_ID: WINDOWS_HOOK_ID = WH_CALLWNDPROC;
+    type Message = CallWndProcMessage;
+    type ClosureStore = GlobalRawClosureStore;
+}
+
+/// A message about to be processed by a window procedure, reported by [`CallWndProcHook`].
+#[cfg(all(feature = "process", feature = "ui"))]
+#[derive(Debug)]
+pub struct CallWndProcMessage {
+    pub window: WindowHandle,
+    pub message: u32,
+    pub w_param: usize,
+    pub l_param: isize,
+    /// Whether the message was sent by the thread owning the window.
+    pub sent_by_current_thread: bool,
+}
+
+#[cfg(all(feature = "process", feature = "ui"))]
+impl From<RawLowLevelMessage> for CallWndProcMessage {
+    fn from(value: RawLowLevelMessage) -> Self {
+        let message_data = unsafe { *(value.l_param as *const CWPSTRUCT) };
+        CallWndProcMessage {
+            window: WindowHandle::from_non_null(message_data.hwnd),
+            message: message_data.message,
+            w_param: message_data.wParam.0,
+            l_param: message_data.lParam.0,
+            sent_by_current_thread: value.w_param != 0,
+        }
+    }
+}
+
 /// Decoded mouse message.
 #[derive(Copy, Clone, Debug)]
 pub struct LowLevelMouseMessage {
     pub action: LowLevelMouseAction,
     pub coords: POINT,
+    /// Raw timestamp in milliseconds since system start, wrapping around after about 49.7 days.
+    ///
+    /// See [`Self::duration_since`] for a wrap-safe comparison.
     pub timestamp_ms: u32,
+    /// Point in time of the event, derived from [`Self::timestamp_ms`].
+    pub instant: Instant,
+}
+
+impl LowLevelMouseMessage {
+    /// Returns the time passed between an earlier message and this one, taking timestamp wrap-around into account.
+    pub fn duration_since(&self, earlier: &Self) -> Duration {
+        timestamp_duration_since(self.timestamp_ms, earlier.timestamp_ms)
+    }
 }
 
 impl From<RawLowLevelMessage> for LowLevelMouseMessage {
@@ -119,15 +219,24 @@ impl From<RawLowLevelMessage> for LowLevelMouseMessage {
             (WM_MBUTTONUP, _) => LowLevelMouseAction::ButtonUp(MouseButton::Middle),
             (WM_XBUTTONUP, 1) => LowLevelMouseAction::ButtonUp(MouseButton::X1),
             (WM_XBUTTONUP, 2) => LowLevelMouseAction::ButtonUp(MouseButton::X2),
+            (WM_LBUTTONDBLCLK, _) => LowLevelMouseAction::DoubleClick(MouseButton::Left),
+            (WM_RBUTTONDBLCLK, _) => LowLevelMouseAction::DoubleClick(MouseButton::Right),
+            (WM_MBUTTONDBLCLK, _) => LowLevelMouseAction::DoubleClick(MouseButton::Middle),
+            (WM_XBUTTONDBLCLK, 1) => LowLevelMouseAction::DoubleClick(MouseButton::X1),
+            (WM_XBUTTONDBLCLK, 2) => LowLevelMouseAction::DoubleClick(MouseButton::X2),
             (WM_MOUSEWHEEL, raw_movement) => {
                 LowLevelMouseAction::WheelScroll(MouseScrollEvent::from_raw_movement(raw_movement))
             }
+            (WM_MOUSEHWHEEL, raw_movement) => LowLevelMouseAction::WheelHScroll(
+                MouseHScrollEvent::from_raw_movement(raw_movement),
+            ),
             (_, _) => LowLevelMouseAction::Other(w_param),
         };
         LowLevelMouseMessage {
             action,
             coords: message_data.pt,
             timestamp_ms: message_data.time,
+            instant: timestamp_to_instant(message_data.time),
         }
     }
 }
@@ -138,7 +247,19 @@ pub struct LowLevelKeyboardMessage {
     pub action: LowLevelKeyboardAction,
     pub key: KeyboardKey,
     pub scan_code: u32,
+    /// Raw timestamp in milliseconds since system start, wrapping around after about 49.7 days.
+    ///
+    /// See [`Self::duration_since`] for a wrap-safe comparison.
     pub timestamp_ms: u32,
+    /// Point in time of the event, derived from [`Self::timestamp_ms`].
+    pub instant: Instant,
+}
+
+impl LowLevelKeyboardMessage {
+    /// Returns the time passed between an earlier message and this one, taking timestamp wrap-around into account.
+    pub fn duration_since(&self, earlier: &Self) -> Duration {
+        timestamp_duration_since(self.timestamp_ms, earlier.timestamp_ms)
+    }
 }
 
 impl From<RawLowLevelMessage> for LowLevelKeyboardMessage {
@@ -152,16 +273,35 @@ impl From<RawLowLevelMessage> for LowLevelKeyboardMessage {
             key,
             scan_code: message_data.scanCode,
             timestamp_ms: message_data.time,
+            instant: timestamp_to_instant(message_data.time),
         }
     }
 }
 
+fn timestamp_duration_since(later_ms: u32, earlier_ms: u32) -> Duration {
+    Duration::from_millis(later_ms.wrapping_sub(earlier_ms).into())
+}
+
+/// Converts a timestamp of a message that was just received.
+fn timestamp_to_instant(timestamp_ms: u32) -> Instant {
+    let now = Instant::now();
+    let current_ms = unsafe { GetTickCount() };
+    now.checked_sub(timestamp_duration_since(current_ms, timestamp_ms))
+        .unwrap_or(now)
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum LowLevelMouseAction {
     Move,
     ButtonDown(MouseButton),
     ButtonUp(MouseButton),
+    /// A double-click message.
+    ///
+    /// Windows usually reports double-clicks to low level hooks as regular button presses, since they are
+    /// only synthesized later for windows with the corresponding class style.
+    DoubleClick(MouseButton),
     WheelScroll(MouseScrollEvent),
+    WheelHScroll(MouseHScrollEvent),
     Other(u32),
 }
 
@@ -319,7 +459,11 @@ mod private {
         type Message: From<RawLowLevelMessage>;
         type ClosureStore: RawClosureStore;
 
-        fn add_hook<const ID: IdType, F>(user_callback: &mut F) -> io::Result<HookHandle<Self>>
+        /// Adds the hook for the given thread of the current process, or all threads of the desktop for `0`.
+        fn add_hook<const ID: IdType, F>(
+            thread_id: u32,
+            user_callback: &mut F,
+        ) -> io::Result<HookHandle<Self>>
         where
             F: FnMut(Self::Message) -> HookReturnValue + Send,
         {
@@ -366,7 +510,7 @@ mod private {
                     Self::TYPE_ID,
                     Some(internal_callback::<ID, Self, F>),
                     HMODULE::default(),
-                    0,
+                    thread_id,
                 )?
             };
             Ok(HookHandle::new(ID, handle))
@@ -433,4 +577,29 @@ mod tests {
         LowLevelMouseHook::run_hook(&mut callback)?;
         Ok(())
     }
+
+    #[cfg(all(feature = "process", feature = "ui"))]
+    #[test]
+    fn call_wnd_proc_hook_and_unhook() -> windows::core::Result<()> {
+        let mut callback = |_message: CallWndProcMessage| {};
+        unsafe {
+            PostThreadMessageW(
+                GetCurrentThreadId(),
+                WM_QUIT,
+                WPARAM::default(),
+                LPARAM::default(),
+            )?
+        };
+        CallWndProcHook::run_hook(ThreadId::current(), &mut callback)?;
+        Ok(())
+    }
+
+    #[test]
+    fn wrapping_timestamp_duration() {
+        assert_eq!(
+            timestamp_duration_since(5, u32::MAX - 4),
+            Duration::from_millis(10)
+        );
+        assert_eq!(timestamp_duration_since(20, 10), Duration::from_millis(10));
+    }
 }
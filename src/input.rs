@@ -1,5 +1,6 @@
 //! Keyboard and hotkeys.
 
+use std::time::Duration;
 use std::{
     io,
     mem,
@@ -9,26 +10,39 @@ use num_enum::{
     FromPrimitive,
     IntoPrimitive,
 };
+use windows::Win32::Foundation::POINT;
 use windows::Win32::UI::Input::KeyboardAndMouse::{
+    ActivateKeyboardLayout,
     GetAsyncKeyState,
+    GetDoubleClickTime,
     GetKeyState,
+    GetKeyboardLayout,
+    GetKeyboardLayoutList,
+    GetKeyboardState,
     SendInput,
+    ACTIVATE_KEYBOARD_LAYOUT_FLAGS,
+    HKL,
     INPUT,
     INPUT_0,
     INPUT_KEYBOARD,
     INPUT_MOUSE,
     KEYBDINPUT,
     KEYEVENTF_KEYUP,
+    MOUSEEVENTF_ABSOLUTE,
+    MOUSEEVENTF_HWHEEL,
     MOUSEEVENTF_LEFTDOWN,
     MOUSEEVENTF_LEFTUP,
     MOUSEEVENTF_MIDDLEDOWN,
     MOUSEEVENTF_MIDDLEUP,
+    MOUSEEVENTF_MOVE,
     MOUSEEVENTF_RIGHTDOWN,
     MOUSEEVENTF_RIGHTUP,
+    MOUSEEVENTF_VIRTUALDESK,
     MOUSEEVENTF_WHEEL,
     MOUSEEVENTF_XDOWN,
     MOUSEEVENTF_XUP,
     MOUSEINPUT,
+    MOUSE_EVENT_FLAGS,
     VIRTUAL_KEY,
     VK_0,
     VK_1,
@@ -145,6 +159,12 @@ use windows::Win32::UI::Input::KeyboardAndMouse::{
     VK_Z,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
+    GetCaretBlinkTime,
+    GetSystemMetrics,
+    SM_CXVIRTUALSCREEN,
+    SM_CYVIRTUALSCREEN,
+    SM_XVIRTUALSCREEN,
+    SM_YVIRTUALSCREEN,
     WHEEL_DELTA,
     XBUTTON1,
     XBUTTON2,
@@ -154,6 +174,8 @@ use crate::internal::ReturnValue;
 use private::*;
 
 pub mod hotkeys;
+#[cfg(feature = "ui")]
+pub mod raw_input;
 
 /// A [`KeyboardKey`] or a [`MouseButton`].
 pub trait GenericKey: GenericKeyInternal {
@@ -182,21 +204,13 @@ pub trait GenericKey: GenericKeyInternal {
     /// This will cause a 'press' event for each key in the list (in the given order),
     /// followed by a sequence of 'release' events (in the inverse order).
     fn send_combination(keys: &[Self]) -> io::Result<()> {
-        let raw_input_pairs: Vec<_> = keys
-            .iter()
-            .copied()
-            .map(|key: Self| {
-                let raw_input = key.get_press_raw_input(false);
-                let raw_input_release = key.get_press_raw_input(true);
-                (raw_input, raw_input_release)
-            })
-            .collect();
-        let raw_inputs: Vec<_> = raw_input_pairs
-            .iter()
-            .map(|x| x.0)
-            .chain(raw_input_pairs.iter().rev().map(|x| x.1))
-            .collect();
-        send_raw_inputs(raw_inputs.as_slice())
+        let sequence = keys.iter().fold(InputSequence::new(), |sequence, key| {
+            sequence.key_down(*key)
+        });
+        keys.iter()
+            .rev()
+            .fold(sequence, |sequence, key| sequence.key_up(*key))
+            .send()
     }
 }
 
@@ -443,6 +457,120 @@ impl From<KeyboardKey> for i32 {
     }
 }
 
+/// A snapshot of the state of all keys and mouse buttons, see [`get_keyboard_state`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct KeyboardState {
+    raw_state: [u8; 256],
+}
+
+impl KeyboardState {
+    /// Returns true if the key or mouse button was pressed at the time of the snapshot.
+    pub fn is_down(&self, key: impl GenericKey) -> bool {
+        self.get_raw_key_state(key) & 0x80 != 0
+    }
+
+    /// Returns true if the key has lock functionality (e.g. Caps Lock) and the lock was toggled at the time of the snapshot.
+    pub fn is_toggled(&self, key: KeyboardKey) -> bool {
+        self.get_raw_key_state(key) & 1 != 0
+    }
+
+    fn get_raw_key_state(&self, key: impl GenericKey) -> u8 {
+        let raw_key: u16 = key.into();
+        self.raw_state[usize::from(raw_key) & 0xFF]
+    }
+}
+
+/// Returns a consistent snapshot of the state of all keys and mouse buttons.
+///
+/// Other than [`GenericKey::is_pressed`], the state is not read asynchronously from the hardware,
+/// but reflects the input messages already retrieved from the current thread's message queue.
+/// It is therefore mainly useful when processing window messages.
+pub fn get_keyboard_state() -> io::Result<KeyboardState> {
+    let mut raw_state = [0; 256];
+    unsafe { GetKeyboardState(&mut raw_state)? };
+    Ok(KeyboardState { raw_state })
+}
+
+/// Returns the maximum time between two clicks for them to count as a double-click.
+///
+/// The second click must also be within the area given by the system metrics
+/// `ui::desktop::SystemMetric::DoubleClickWidth` and `DoubleClickHeight`.
+pub fn get_double_click_time() -> Duration {
+    let millis = unsafe { GetDoubleClickTime() };
+    Duration::from_millis(millis.into())
+}
+
+/// Returns the time between the caret being shown and hidden while blinking,
+/// or `None` if the caret does not blink.
+pub fn get_caret_blink_time() -> io::Result<Option<Duration>> {
+    const NO_BLINKING: u32 = u32::MAX;
+    let millis = unsafe { GetCaretBlinkTime() }.if_null_get_last_error()?;
+    if millis == NO_BLINKING {
+        Ok(None)
+    } else {
+        Ok(Some(Duration::from_millis(millis.into())))
+    }
+}
+
+/// An input locale identifier, consisting of a language and a physical keyboard layout.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct KeyboardLayout {
+    raw_handle: HKL,
+}
+
+impl KeyboardLayout {
+    /// Returns the active keyboard layout of the current thread.
+    pub fn get_current() -> Self {
+        let raw_handle = unsafe { GetKeyboardLayout(0) };
+        Self { raw_handle }
+    }
+
+    /// Returns the language identifier (`LANGID`) of the layout, e.g. `0x0409` for English (United States).
+    pub fn get_language_id(&self) -> u16 {
+        (self.raw_handle.0 as usize & 0xFFFF) as u16
+    }
+}
+
+/// Returns the keyboard layouts currently installed for the user, as shown in the language bar.
+pub fn list_keyboard_layouts() -> io::Result<Vec<KeyboardLayout>> {
+    let count = unsafe { GetKeyboardLayoutList(None) };
+    let mut raw_layouts: Vec<HKL> = vec![HKL::default(); count.try_into().unwrap()];
+    let count =
+        unsafe { GetKeyboardLayoutList(Some(&mut raw_layouts)) }.if_null_get_last_error()?;
+    raw_layouts.truncate(count.try_into().unwrap());
+    Ok(raw_layouts
+        .into_iter()
+        .map(|raw_handle| KeyboardLayout { raw_handle })
+        .collect())
+}
+
+/// Activates the keyboard layout for the current thread, returning the previously active layout.
+///
+/// The layout must be one of the installed layouts, see [`list_keyboard_layouts`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use winapi_easy::input::{
+///     activate_layout,
+///     list_keyboard_layouts,
+/// };
+///
+/// let layouts = list_keyboard_layouts()?;
+/// if let Some(us_layout) = layouts.iter().find(|layout| layout.get_language_id() == 0x0409) {
+///     activate_layout(*us_layout)?;
+/// }
+///
+/// # Result::<(), std::io::Error>::Ok(())
+/// ```
+pub fn activate_layout(layout: KeyboardLayout) -> io::Result<KeyboardLayout> {
+    let previous_handle =
+        unsafe { ActivateKeyboardLayout(layout.raw_handle, ACTIVATE_KEYBOARD_LAYOUT_FLAGS(0))? };
+    Ok(KeyboardLayout {
+        raw_handle: previous_handle,
+    })
+}
+
 fn send_raw_inputs(raw_inputs: &[INPUT]) -> io::Result<()> {
     let raw_input_size = mem::size_of::<INPUT>()
         .try_into()
@@ -460,6 +588,130 @@ fn send_raw_inputs(raw_inputs: &[INPUT]) -> io::Result<()> {
     Ok(())
 }
 
+/// A sequence of keyboard and mouse inputs to be sent together.
+///
+/// All inputs are sent with a single call, so they cannot be interleaved with other inputs,
+/// whether from the user or other applications.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winapi_easy::input::{
+///     InputSequence,
+///     KeyboardKey,
+///     MouseButton,
+/// };
+/// use windows::Win32::Foundation::POINT;
+///
+/// InputSequence::new()
+///     .mouse_move(POINT { x: 100, y: 100 })
+///     .mouse_button(MouseButton::Left)
+///     .key_down(KeyboardKey::LeftCtrl)
+///     .key_down(KeyboardKey::A)
+///     .key_up(KeyboardKey::A)
+///     .key_up(KeyboardKey::LeftCtrl)
+///     .send()?;
+///
+/// # Result::<(), std::io::Error>::Ok(())
+/// ```
+#[derive(Clone, Default)]
+pub struct InputSequence {
+    raw_inputs: Vec<INPUT>,
+}
+
+impl InputSequence {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a 'press' event for a key or mouse button.
+    pub fn key_down(self, key: impl GenericKey) -> Self {
+        self.with_raw_input(key.get_press_raw_input(false))
+    }
+
+    /// Adds a 'release' event for a key or mouse button.
+    pub fn key_up(self, key: impl GenericKey) -> Self {
+        self.with_raw_input(key.get_press_raw_input(true))
+    }
+
+    /// Adds a click of a mouse button, meaning a 'press' followed by a 'release' event.
+    pub fn mouse_button(self, button: MouseButton) -> Self {
+        self.key_down(button).key_up(button)
+    }
+
+    /// Adds a mouse movement to the given absolute screen coordinates.
+    ///
+    /// Coordinates on all monitors of the virtual screen are supported.
+    pub fn mouse_move(self, target: POINT) -> Self {
+        let (left, top, width, height) = unsafe {
+            (
+                GetSystemMetrics(SM_XVIRTUALSCREEN),
+                GetSystemMetrics(SM_YVIRTUALSCREEN),
+                GetSystemMetrics(SM_CXVIRTUALSCREEN),
+                GetSystemMetrics(SM_CYVIRTUALSCREEN),
+            )
+        };
+        // Absolute coordinates are normalized to 0..=65535 over the whole virtual screen
+        let normalize = |value: i32, offset: i32, size: i32| -> i32 {
+            let max_value = i64::from(size - 1).max(1);
+            let normalized = (i64::from(value - offset) * 65535 + max_value / 2) / max_value;
+            normalized.clamp(0, 65535) as i32
+        };
+        let raw_input = INPUT {
+            r#type: INPUT_MOUSE,
+            Anonymous: INPUT_0 {
+                mi: MOUSEINPUT {
+                    dx: normalize(target.x, left, width),
+                    dy: normalize(target.y, top, height),
+                    dwFlags: MOUSEEVENTF_MOVE | MOUSEEVENTF_ABSOLUTE | MOUSEEVENTF_VIRTUALDESK,
+                    ..Default::default()
+                },
+            },
+        };
+        self.with_raw_input(raw_input)
+    }
+
+    /// Adds a certain amount of scroll events.
+    pub fn scroll(self, event: MouseScrollEvent, amount: u8) -> Self {
+        self.with_raw_input(event.get_raw_input(amount))
+    }
+
+    /// Adds a certain amount of horizontal scroll events.
+    pub fn hscroll(self, event: MouseHScrollEvent, amount: u8) -> Self {
+        self.with_raw_input(event.get_raw_input(amount))
+    }
+
+    /// Globally sends all inputs of the sequence as if the user had performed them.
+    pub fn send(&self) -> io::Result<()> {
+        if self.raw_inputs.is_empty() {
+            return Ok(());
+        }
+        send_raw_inputs(&self.raw_inputs)
+    }
+
+    fn with_raw_input(mut self, raw_input: INPUT) -> Self {
+        self.raw_inputs.push(raw_input);
+        self
+    }
+}
+
+/// Globally sends a mouse drag from one absolute screen position to another.
+///
+/// The button is pressed at the start position and released at the target position,
+/// with intermediate movements in between. All inputs are sent as a single [`InputSequence`].
+pub fn drag(from: POINT, to: POINT, button: MouseButton) -> io::Result<()> {
+    const MOVE_STEPS: i32 = 10;
+    let sequence = InputSequence::new().mouse_move(from).key_down(button);
+    (1..=MOVE_STEPS)
+        .map(|step| POINT {
+            x: from.x + (to.x - from.x) * step / MOVE_STEPS,
+            y: from.y + (to.y - from.y) * step / MOVE_STEPS,
+        })
+        .fold(sequence, |sequence, point| sequence.mouse_move(point))
+        .key_up(button)
+        .send()
+}
+
 /// Mouse button.
 ///
 /// Note that X-Buttons above #2 are only handled by the mouse driver.
@@ -498,8 +750,6 @@ pub enum MouseScrollEvent {
 }
 
 impl MouseScrollEvent {
-    const WHEEL_DELTA_INT: i16 = WHEEL_DELTA as _;
-
     /// Globally sends a single scroll event.
     pub fn send(self) -> io::Result<()> {
         self.send_amount(1)
@@ -507,31 +757,21 @@ impl MouseScrollEvent {
 
     /// Globally sends a certain amount of scroll events.
     pub fn send_amount(self, amount: u8) -> io::Result<()> {
+        InputSequence::new().scroll(self, amount).send()
+    }
+
+    fn get_raw_input(self, amount: u8) -> INPUT {
         let single_delta = match self {
-            MouseScrollEvent::Up => Self::WHEEL_DELTA_INT,
-            MouseScrollEvent::Down => -Self::WHEEL_DELTA_INT,
+            MouseScrollEvent::Up => WHEEL_DELTA_INT,
+            MouseScrollEvent::Down => -WHEEL_DELTA_INT,
             MouseScrollEvent::Continuous(delta) => delta,
         };
-        // Should never overflow due to data types
-        let mouse_data = i32::from(single_delta) * i32::from(amount);
-        let raw_input = INPUT {
-            r#type: INPUT_MOUSE,
-            Anonymous: INPUT_0 {
-                mi: MOUSEINPUT {
-                    // bit-cast semantics necessary here because negative values should be allowed
-                    mouseData: mouse_data as u32,
-                    dwFlags: MOUSEEVENTF_WHEEL,
-                    ..Default::default()
-                },
-            },
-        };
-        send_raw_inputs(&[raw_input])
+        get_wheel_raw_input(single_delta, amount, MOUSEEVENTF_WHEEL)
     }
 
     #[allow(dead_code)]
     pub(crate) fn from_raw_movement(raw_movement: u16) -> Self {
         let raw_movement = raw_movement as i16;
-        const WHEEL_DELTA_INT: i16 = WHEEL_DELTA as _;
         if raw_movement == WHEEL_DELTA_INT {
             MouseScrollEvent::Up
         } else if raw_movement == -WHEEL_DELTA_INT {
@@ -541,3 +781,69 @@ impl MouseScrollEvent {
         }
     }
 }
+
+/// Mouse horizontal scroll wheel 'left' or 'right' event, possibly continuous.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MouseHScrollEvent {
+    /// Single left-scroll event.
+    ///
+    /// Equivalent to [`Self::Continuous`] with a value of -[`WHEEL_DELTA`].
+    Left,
+    /// Single right-scroll event.
+    ///
+    /// Equivalent to [`Self::Continuous`] with a value of [`WHEEL_DELTA`].
+    Right,
+    /// Continuous 'right' (positive value) or 'left' (negative value) scroll event.
+    Continuous(i16),
+}
+
+impl MouseHScrollEvent {
+    /// Globally sends a single horizontal scroll event.
+    pub fn send(self) -> io::Result<()> {
+        self.send_amount(1)
+    }
+
+    /// Globally sends a certain amount of horizontal scroll events.
+    pub fn send_amount(self, amount: u8) -> io::Result<()> {
+        InputSequence::new().hscroll(self, amount).send()
+    }
+
+    fn get_raw_input(self, amount: u8) -> INPUT {
+        let single_delta = match self {
+            MouseHScrollEvent::Left => -WHEEL_DELTA_INT,
+            MouseHScrollEvent::Right => WHEEL_DELTA_INT,
+            MouseHScrollEvent::Continuous(delta) => delta,
+        };
+        get_wheel_raw_input(single_delta, amount, MOUSEEVENTF_HWHEEL)
+    }
+
+    #[allow(dead_code)]
+    pub(crate) fn from_raw_movement(raw_movement: u16) -> Self {
+        let raw_movement = raw_movement as i16;
+        if raw_movement == WHEEL_DELTA_INT {
+            MouseHScrollEvent::Right
+        } else if raw_movement == -WHEEL_DELTA_INT {
+            MouseHScrollEvent::Left
+        } else {
+            MouseHScrollEvent::Continuous(raw_movement)
+        }
+    }
+}
+
+const WHEEL_DELTA_INT: i16 = WHEEL_DELTA as _;
+
+fn get_wheel_raw_input(single_delta: i16, amount: u8, flags: MOUSE_EVENT_FLAGS) -> INPUT {
+    // Should never overflow due to data types
+    let mouse_data = i32::from(single_delta) * i32::from(amount);
+    INPUT {
+        r#type: INPUT_MOUSE,
+        Anonymous: INPUT_0 {
+            mi: MOUSEINPUT {
+                // bit-cast semantics necessary here because negative values should be allowed
+                mouseData: mouse_data as u32,
+                dwFlags: flags,
+                ..Default::default()
+            },
+        },
+    }
+}
@@ -103,7 +103,7 @@ where
                             RegisterHotKey(
                                 None,
                                 curr_id,
-                                HOT_KEY_MODIFIERS(hotkey_def.key_combination.modifiers.0),
+                                hotkey_def.key_combination.get_raw_modifiers(),
                                 hotkey_def.key_combination.key.into(),
                             )
                             .map_err(From::from)
@@ -190,20 +190,45 @@ pub enum Modifier {
 pub struct ModifierCombination(u32);
 
 /// A combination of zero or more modifiers and exactly one normal key.
+///
+/// By default, keyboard auto-repeat does not yield multiple hotkey notifications.
+/// This can be changed with [`KeyCombination::with_auto_repeat`].
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub struct KeyCombination {
     modifiers: ModifierCombination,
     key: KeyboardKey,
+    auto_repeat: bool,
 }
 
 impl KeyCombination {
     fn new_from(modifiers: ModifierCombination, key: KeyboardKey) -> Self {
         KeyCombination {
-            // Changes the hotkey behavior so that the keyboard auto-repeat does not yield multiple hotkey notifications.
-            modifiers: ModifierCombination(modifiers.0 | MOD_NOREPEAT.0),
+            modifiers,
             key,
+            auto_repeat: false,
         }
     }
+
+    /// Sets whether holding down the key combination yields repeated hotkey notifications.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use winapi_easy::input::hotkeys::{GlobalHotkeySet, Modifier};
+    /// use winapi_easy::input::KeyboardKey;
+    ///
+    /// let hotkeys = GlobalHotkeySet::new()
+    ///     .add_hotkey((), (Modifier::Ctrl + KeyboardKey::A).with_auto_repeat(true));
+    /// ```
+    pub fn with_auto_repeat(mut self, auto_repeat: bool) -> Self {
+        self.auto_repeat = auto_repeat;
+        self
+    }
+
+    fn get_raw_modifiers(&self) -> HOT_KEY_MODIFIERS {
+        let no_repeat_flag = if self.auto_repeat { 0 } else { MOD_NOREPEAT.0 };
+        HOT_KEY_MODIFIERS(self.modifiers.0 | no_repeat_flag)
+    }
 }
 
 impl From<Modifier> for ModifierCombination {
@@ -256,3 +281,21 @@ impl Add<KeyboardKey> for Modifier {
         KeyCombination::new_from(self.into(), rhs)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_combination_auto_repeat() {
+        let combination = Modifier::Ctrl + Modifier::Alt + KeyboardKey::A;
+        assert_eq!(
+            combination.get_raw_modifiers(),
+            MOD_CONTROL | MOD_ALT | MOD_NOREPEAT
+        );
+        assert_eq!(
+            combination.with_auto_repeat(true).get_raw_modifiers(),
+            MOD_CONTROL | MOD_ALT
+        );
+    }
+}
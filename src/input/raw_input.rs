@@ -0,0 +1,333 @@
+//! Raw input from keyboards and mice.
+//!
+//! In contrast to the hooks in [`crate::hooking`], raw input is delivered to a specific window,
+//! by default only while it is in the foreground.
+
+use std::ffi::c_void;
+use std::io;
+use std::mem;
+
+use windows::Win32::Foundation::{
+    HANDLE,
+    HWND,
+};
+use windows::Win32::UI::Input::{
+    GetRawInputData,
+    GetRawInputDeviceInfoW,
+    RegisterRawInputDevices,
+    HRAWINPUT,
+    MOUSE_MOVE_ABSOLUTE,
+    RAWINPUT,
+    RAWINPUTDEVICE,
+    RAWINPUTDEVICE_FLAGS,
+    RAWINPUTHEADER,
+    RAWKEYBOARD,
+    RAWMOUSE,
+    RIDEV_DEVNOTIFY,
+    RIDEV_INPUTSINK,
+    RIDEV_REMOVE,
+    RIDI_DEVICENAME,
+    RID_INPUT,
+    RIM_TYPEKEYBOARD,
+    RIM_TYPEMOUSE,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    RI_KEY_BREAK,
+    RI_MOUSE_BUTTON_4_DOWN,
+    RI_MOUSE_BUTTON_4_UP,
+    RI_MOUSE_BUTTON_5_DOWN,
+    RI_MOUSE_BUTTON_5_UP,
+    RI_MOUSE_LEFT_BUTTON_DOWN,
+    RI_MOUSE_LEFT_BUTTON_UP,
+    RI_MOUSE_MIDDLE_BUTTON_DOWN,
+    RI_MOUSE_MIDDLE_BUTTON_UP,
+    RI_MOUSE_RIGHT_BUTTON_DOWN,
+    RI_MOUSE_RIGHT_BUTTON_UP,
+    RI_MOUSE_WHEEL,
+};
+
+use crate::input::{
+    KeyboardKey,
+    MouseButton,
+    MouseScrollEvent,
+};
+use crate::internal::ReturnValue;
+use crate::string::FromWideString;
+use crate::ui::WindowHandle;
+
+/// A type of device that raw input can be received from.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum RawInputDevice {
+    Mouse,
+    Keyboard,
+    /// A game controller reported as joystick, whose input is reported as [`RawInput::Other`].
+    Joystick,
+    /// A game controller reported as gamepad, whose input is reported as [`RawInput::Other`].
+    Gamepad,
+}
+
+impl RawInputDevice {
+    const GENERIC_DESKTOP_USAGE_PAGE: u16 = 0x01;
+
+    fn get_usage(self) -> u16 {
+        match self {
+            RawInputDevice::Mouse => 0x02,
+            RawInputDevice::Joystick => 0x04,
+            RawInputDevice::Gamepad => 0x05,
+            RawInputDevice::Keyboard => 0x06,
+        }
+    }
+
+    fn to_raw(self, window: HWND, flags: RAWINPUTDEVICE_FLAGS) -> RAWINPUTDEVICE {
+        RAWINPUTDEVICE {
+            usUsagePage: Self::GENERIC_DESKTOP_USAGE_PAGE,
+            usUsage: self.get_usage(),
+            dwFlags: flags,
+            hwndTarget: window,
+        }
+    }
+}
+
+/// Registers a window to receive raw input from the given device types.
+///
+/// The input will be sent to [`crate::ui::messaging::WindowMessageListener::handle_raw_input`].
+/// A device type can only be registered for one window per process at a time, registering it again replaces the window.
+///
+/// If `receive_in_background` is `true`, input is also received while the window is not in the foreground.
+pub fn register(
+    window: &WindowHandle,
+    devices: &[RawInputDevice],
+    receive_in_background: bool,
+) -> io::Result<()> {
+    let options = RawInputOptions {
+        receive_in_background,
+        ..Default::default()
+    };
+    register_with_options(window, devices, options)
+}
+
+/// Registers a window to receive raw input from the given device types, see [`register`].
+pub fn register_with_options(
+    window: &WindowHandle,
+    devices: &[RawInputDevice],
+    options: RawInputOptions,
+) -> io::Result<()> {
+    let mut flags = RAWINPUTDEVICE_FLAGS::default();
+    if options.receive_in_background {
+        flags |= RIDEV_INPUTSINK;
+    }
+    if options.receive_device_changes {
+        flags |= RIDEV_DEVNOTIFY;
+    }
+    let raw_devices: Vec<RAWINPUTDEVICE> = devices
+        .iter()
+        .map(|device| device.to_raw(window.into(), flags))
+        .collect();
+    register_raw(&raw_devices)
+}
+
+/// Options for [`register_with_options`].
+#[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
+pub struct RawInputOptions {
+    /// Receive input also while the window is not in the foreground.
+    pub receive_in_background: bool,
+    /// Receive notifications when devices of the registered types are added or removed,
+    /// see [`crate::ui::messaging::WindowMessageListener::handle_input_device_change`].
+    pub receive_device_changes: bool,
+}
+
+/// A device delivering raw input, e.g. as reported when added or removed.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct RawInputDeviceHandle {
+    raw_handle: HANDLE,
+}
+
+impl RawInputDeviceHandle {
+    pub(crate) fn from_raw(raw_handle: HANDLE) -> Self {
+        Self { raw_handle }
+    }
+
+    /// Returns the device interface name, which can be used to open the device.
+    ///
+    /// Fails if the device was already removed.
+    pub fn get_name(&self) -> io::Result<String> {
+        let mut num_chars: u32 = 0;
+        unsafe { GetRawInputDeviceInfoW(self.raw_handle, RIDI_DEVICENAME, None, &mut num_chars) }
+            .if_eq_to_error(u32::MAX, io::Error::last_os_error)?;
+        let mut buffer: Vec<u16> = vec![0; num_chars as usize];
+        let copied_chars = unsafe {
+            GetRawInputDeviceInfoW(
+                self.raw_handle,
+                RIDI_DEVICENAME,
+                Some(buffer.as_mut_ptr().cast::<c_void>()),
+                &mut num_chars,
+            )
+        };
+        copied_chars.if_eq_to_error(u32::MAX, io::Error::last_os_error)?;
+        // The returned count includes the terminating zero
+        buffer.truncate(copied_chars.saturating_sub(1) as usize);
+        Ok(buffer.to_string_lossy())
+    }
+}
+
+/// Stops receiving raw input from the given device types.
+pub fn unregister(devices: &[RawInputDevice]) -> io::Result<()> {
+    let raw_devices: Vec<RAWINPUTDEVICE> = devices
+        .iter()
+        .map(|device| device.to_raw(HWND::default(), RIDEV_REMOVE))
+        .collect();
+    register_raw(&raw_devices)
+}
+
+fn register_raw(raw_devices: &[RAWINPUTDEVICE]) -> io::Result<()> {
+    unsafe {
+        RegisterRawInputDevices(
+            raw_devices,
+            mem::size_of::<RAWINPUTDEVICE>().try_into().unwrap(),
+        )?;
+    }
+    Ok(())
+}
+
+/// Decoded raw input.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum RawInput {
+    Mouse(RawMouseInput),
+    Keyboard(RawKeyboardInput),
+    /// Input from other HID devices.
+    Other,
+}
+
+impl RawInput {
+    pub(crate) fn from_handle(handle: HRAWINPUT) -> io::Result<Self> {
+        let header_size: u32 = mem::size_of::<RAWINPUTHEADER>().try_into().unwrap();
+        let mut size: u32 = 0;
+        unsafe { GetRawInputData(handle, RID_INPUT, None, &mut size, header_size) }
+            .if_eq_to_error(u32::MAX, io::Error::last_os_error)?;
+        // Data for HID devices may be bigger than `RAWINPUT`, keyboard and mouse data may be smaller
+        let buffer_size = (size as usize).max(mem::size_of::<RAWINPUT>());
+        let mut buffer: Vec<u64> = vec![0; buffer_size.div_ceil(mem::size_of::<u64>())];
+        unsafe {
+            GetRawInputData(
+                handle,
+                RID_INPUT,
+                Some(buffer.as_mut_ptr().cast::<c_void>()),
+                &mut size,
+                header_size,
+            )
+        }
+        .if_eq_to_error(u32::MAX, io::Error::last_os_error)?;
+        let raw_input = unsafe { *buffer.as_ptr().cast::<RAWINPUT>() };
+        let decoded = match raw_input.header.dwType {
+            x if x == RIM_TYPEMOUSE.0 => {
+                RawInput::Mouse(RawMouseInput::from(unsafe { raw_input.data.mouse }))
+            }
+            x if x == RIM_TYPEKEYBOARD.0 => {
+                RawInput::Keyboard(RawKeyboardInput::from(unsafe { raw_input.data.keyboard }))
+            }
+            _ => RawInput::Other,
+        };
+        Ok(decoded)
+    }
+}
+
+/// Raw mouse input.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct RawMouseInput {
+    /// Movement since the last event, or absolute coordinates if [`Self::is_absolute`] is `true`.
+    ///
+    /// Relative movement is in device units and not affected by mouse acceleration settings.
+    pub movement: (i32, i32),
+    /// Absolute coordinates are normalized to `0..=65535`, used by touch devices and virtual machines.
+    pub is_absolute: bool,
+    pub buttons_pressed: Vec<MouseButton>,
+    pub buttons_released: Vec<MouseButton>,
+    pub scroll: Option<MouseScrollEvent>,
+}
+
+impl From<RAWMOUSE> for RawMouseInput {
+    fn from(value: RAWMOUSE) -> Self {
+        const BUTTON_FLAGS: [(u32, u32, MouseButton); 5] = [
+            (
+                RI_MOUSE_LEFT_BUTTON_DOWN,
+                RI_MOUSE_LEFT_BUTTON_UP,
+                MouseButton::Left,
+            ),
+            (
+                RI_MOUSE_RIGHT_BUTTON_DOWN,
+                RI_MOUSE_RIGHT_BUTTON_UP,
+                MouseButton::Right,
+            ),
+            (
+                RI_MOUSE_MIDDLE_BUTTON_DOWN,
+                RI_MOUSE_MIDDLE_BUTTON_UP,
+                MouseButton::Middle,
+            ),
+            (
+                RI_MOUSE_BUTTON_4_DOWN,
+                RI_MOUSE_BUTTON_4_UP,
+                MouseButton::X1,
+            ),
+            (
+                RI_MOUSE_BUTTON_5_DOWN,
+                RI_MOUSE_BUTTON_5_UP,
+                MouseButton::X2,
+            ),
+        ];
+        let (button_flags, button_data) = unsafe {
+            (
+                u32::from(value.Anonymous.Anonymous.usButtonFlags),
+                value.Anonymous.Anonymous.usButtonData,
+            )
+        };
+        let buttons_with_flag = |get_flag: fn(&(u32, u32, MouseButton)) -> u32| {
+            BUTTON_FLAGS
+                .iter()
+                .filter(|entry| button_flags & get_flag(entry) != 0)
+                .map(|entry| entry.2)
+                .collect()
+        };
+        Self {
+            movement: (value.lLastX, value.lLastY),
+            is_absolute: value.usFlags.0 & MOUSE_MOVE_ABSOLUTE.0 != 0,
+            buttons_pressed: buttons_with_flag(|entry| entry.0),
+            buttons_released: buttons_with_flag(|entry| entry.1),
+            scroll: (button_flags & RI_MOUSE_WHEEL != 0)
+                .then(|| MouseScrollEvent::from_raw_movement(button_data)),
+        }
+    }
+}
+
+/// Raw keyboard input.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct RawKeyboardInput {
+    pub key: KeyboardKey,
+    pub scan_code: u16,
+    pub is_release: bool,
+}
+
+impl From<RAWKEYBOARD> for RawKeyboardInput {
+    fn from(value: RAWKEYBOARD) -> Self {
+        Self {
+            key: KeyboardKey::from(value.VKey),
+            scan_code: value.MakeCode,
+            is_release: u32::from(value.Flags) & RI_KEY_BREAK != 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::tests::with_test_window;
+
+    #[test]
+    fn register_and_unregister() -> io::Result<()> {
+        with_test_window(|window| {
+            let devices = [RawInputDevice::Mouse, RawInputDevice::Keyboard];
+            register(window.as_ref(), &devices, true)?;
+            unregister(&devices)?;
+            Ok(())
+        })
+    }
+}
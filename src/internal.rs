@@ -2,13 +2,19 @@
 
 use std::cell::Cell;
 use std::ffi::c_void;
+#[cfg(any(feature = "clipboard", feature = "ui"))]
+use std::ffi::OsString;
 use std::fmt::Display;
 use std::io;
 use std::io::ErrorKind;
+#[cfg(any(feature = "clipboard", feature = "ui"))]
+use std::os::windows::ffi::OsStringExt;
 use std::panic::{
     catch_unwind,
     AssertUnwindSafe,
 };
+#[cfg(any(feature = "clipboard", feature = "ui"))]
+use std::path::PathBuf;
 use std::ptr;
 use std::ptr::NonNull;
 
@@ -27,6 +33,11 @@ use windows::Win32::System::Memory::{
     GlobalLock,
     GlobalUnlock,
 };
+#[cfg(any(feature = "clipboard", feature = "ui"))]
+use windows::Win32::UI::Shell::{
+    DragQueryFileW,
+    HDROP,
+};
 use windows::Win32::UI::WindowsAndMessaging::HMENU;
 
 pub(crate) trait ReturnValue: PartialEq + Sized + Copy {
@@ -134,6 +145,10 @@ impl ReturnValue for isize {
     const NULL_VALUE: Self = 0;
 }
 
+impl ReturnValue for usize {
+    const NULL_VALUE: Self = 0;
+}
+
 impl<T> ReturnValue for *mut T {
     const NULL_VALUE: Self = ptr::null_mut();
 
@@ -415,6 +430,28 @@ pub(crate) fn catch_unwind_and_abort<F: FnOnce() -> R, R>(f: F) -> R {
     }
 }
 
+/// Returns the file paths contained in a drop handle, as used by the clipboard and drag & drop.
+#[cfg(any(feature = "clipboard", feature = "ui"))]
+pub(crate) fn get_hdrop_file_list(hdrop: HDROP) -> io::Result<Vec<PathBuf>> {
+    let num_files = unsafe { DragQueryFileW(hdrop, u32::MAX, None) };
+    (0..num_files)
+        .map(|file_index| {
+            let required_size = unsafe { 1 + DragQueryFileW(hdrop, file_index, None) }
+                .if_null_to_error(|| io::ErrorKind::Other.into())?;
+            let file_str_buf = {
+                let mut buffer = vec![0; required_size as usize];
+                unsafe { DragQueryFileW(hdrop, file_index, Some(buffer.as_mut_slice())) }
+                    .if_null_to_error(|| io::ErrorKind::Other.into())?;
+                // Set length, remove terminating zero
+                buffer.truncate(buffer.len() - 1);
+                buffer
+            };
+            let os_string = OsString::from_wide(&file_str_buf);
+            Ok(PathBuf::from(os_string))
+        })
+        .collect()
+}
+
 pub(crate) fn custom_err_with_code<C>(err_text: &str, result_code: C) -> io::Error
 where
     C: Display,
@@ -24,8 +24,12 @@ pub mod input;
 #[cfg(feature = "media")]
 pub mod media;
 pub mod messaging;
+#[cfg(feature = "power")]
+pub mod power;
 #[cfg(feature = "process")]
 pub mod process;
+#[cfg(feature = "registry")]
+pub mod registry;
 #[cfg(feature = "shell")]
 pub mod shell;
 #[cfg(feature = "ui")]
@@ -33,11 +37,3 @@ pub mod ui;
 
 mod internal;
 mod string;
-
-// Workaround for the `windows::core::imp::interface_hierarchy` macro
-#[cfg(feature = "media")]
-extern crate self as windows_core;
-#[cfg(feature = "media")]
-mod imp {
-    pub(crate) use windows::core::imp::CanInto;
-}
@@ -11,7 +11,10 @@ use std::os::windows::ffi::{
     OsStrExt,
     OsStringExt,
 };
+use std::path::Path;
+use std::time::Duration;
 
+use num_enum::IntoPrimitive;
 use windows::core::{
     GUID,
     PCWSTR,
@@ -30,19 +33,39 @@ use windows::Win32::Media::Audio::{
     IMMDevice,
     IMMDeviceEnumerator,
     MMDeviceEnumerator,
+    PlaySoundW,
     DEVICE_STATE_ACTIVE,
+    SND_ASYNC,
+    SND_FILENAME,
+    SND_FLAGS,
+    SND_LOOP,
+    SND_NODEFAULT,
+    SND_SYNC,
 };
 use windows::Win32::System::Com::STGM_READ;
+use windows::Win32::System::Diagnostics::Debug::{
+    Beep,
+    MessageBeep,
+};
 use windows::Win32::UI::ColorSystem::{
     GetDeviceGammaRamp,
     SetDeviceGammaRamp,
 };
+use windows::Win32::UI::WindowsAndMessaging::{
+    MB_ICONASTERISK,
+    MB_ICONEXCLAMATION,
+    MB_ICONHAND,
+    MB_ICONQUESTION,
+    MB_OK,
+    MESSAGEBOX_STYLE,
+};
 
 use crate::com::{
     ComInterfaceExt,
     ComTaskMemory,
 };
 use crate::internal::ReturnValue;
+use crate::string::ZeroTerminatedWideString;
 
 #[derive(Debug)]
 pub(crate) struct ScreenDeviceContext {
@@ -169,6 +192,93 @@ impl PartialEq for AudioOutputDevice {
     }
 }
 
+/// A sound configured in the Windows sound settings, see [`play_system_sound`].
+#[derive(IntoPrimitive, Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(u32)]
+pub enum SystemSound {
+    Default = MB_OK.0,
+    /// Used for information messages.
+    Asterisk = MB_ICONASTERISK.0,
+    /// Used for warnings.
+    Exclamation = MB_ICONEXCLAMATION.0,
+    Question = MB_ICONQUESTION.0,
+    /// Used for errors.
+    Hand = MB_ICONHAND.0,
+}
+
+/// Plays one of the system sounds asynchronously.
+pub fn play_system_sound(sound: SystemSound) -> io::Result<()> {
+    unsafe { MessageBeep(MESSAGEBOX_STYLE(sound.into()))? };
+    Ok(())
+}
+
+/// Plays a simple tone on the speakers, blocking until it is finished.
+///
+/// The frequency must be between 37 and 32767 Hz.
+pub fn beep(frequency_hz: u32, duration: Duration) -> io::Result<()> {
+    let millis = duration.as_millis().try_into().unwrap_or(u32::MAX);
+    unsafe { Beep(frequency_hz, millis)? };
+    Ok(())
+}
+
+/// How to play a sound file with [`play_wav`].
+#[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
+pub enum PlayMode {
+    /// Blocks until the sound is finished.
+    #[default]
+    Blocking,
+    /// Returns immediately while the sound plays in the background.
+    Async,
+    /// Plays the sound in the background repeatedly until [`stop_sound`] is called.
+    Loop,
+}
+
+impl From<PlayMode> for SND_FLAGS {
+    fn from(value: PlayMode) -> Self {
+        match value {
+            PlayMode::Blocking => SND_SYNC,
+            PlayMode::Async => SND_ASYNC,
+            PlayMode::Loop => SND_ASYNC | SND_LOOP,
+        }
+    }
+}
+
+/// Plays a WAV file.
+///
+/// Starting a new sound stops any sound previously started by this process.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winapi_easy::media::{
+///     play_wav,
+///     PlayMode,
+/// };
+///
+/// play_wav("C:\\Windows\\Media\\Alarm01.wav", PlayMode::Async)?;
+///
+/// # Result::<(), std::io::Error>::Ok(())
+/// ```
+pub fn play_wav<P: AsRef<Path>>(path: P, mode: PlayMode) -> io::Result<()> {
+    let wide_path = ZeroTerminatedWideString::from_os_str(path.as_ref());
+    unsafe {
+        PlaySoundW(
+            wide_path.as_raw_pcwstr(),
+            None,
+            SND_FILENAME | SND_NODEFAULT | mode.into(),
+        )
+        .if_null_to_error_else_drop(|| io::ErrorKind::NotFound.into())?;
+    }
+    Ok(())
+}
+
+/// Stops the sound currently played by [`play_wav`], if any.
+pub fn stop_sound() {
+    unsafe {
+        let _ = PlaySoundW(PCWSTR::null(), None, SND_SYNC);
+    }
+}
+
 mod policy_config {
     #![allow(non_upper_case_globals, non_snake_case)]
 
@@ -262,6 +372,11 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn play_missing_wav() {
+        assert!(play_wav("nonexistent_winapi_easy_test.wav", PlayMode::Blocking).is_err());
+    }
+
     #[test]
     fn check_get_global_default() {
         // Accept errors here since there may be no default
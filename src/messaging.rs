@@ -1,19 +1,33 @@
 //! Messaging and message loops.
 
 use std::cell::Cell;
-use windows::Win32::Foundation::BOOL;
+use windows::Win32::Foundation::{
+    BOOL,
+    HWND,
+    LPARAM,
+    WPARAM,
+};
 use windows::Win32::UI::WindowsAndMessaging::{
     DispatchMessageW,
     GetMessageW,
     PostQuitMessage,
+    SendMessageTimeoutW,
     TranslateMessage,
+    HWND_BROADCAST,
     MSG,
+    SMTO_ABORTIFHUNG,
     WM_QUIT,
+    WM_SETTINGCHANGE,
 };
 
 use std::io;
+#[cfg(feature = "ui")]
+use std::ops::RangeInclusive;
 
 use crate::internal::ReturnValue;
+use crate::string::ToWideString;
+#[cfg(feature = "ui")]
+use crate::ui::WindowHandle;
 
 /// Windows thread message loop functions.
 ///
@@ -36,7 +50,47 @@ impl ThreadMessageLoop {
     /// # Panics
     ///
     /// Will panic if the message loop is already running.
-    pub fn run_thread_message_loop<F>(mut loop_callback: F) -> io::Result<()>
+    pub fn run_thread_message_loop<F>(loop_callback: F) -> io::Result<()>
+    where
+        F: FnMut() -> io::Result<()>,
+    {
+        Self::run_with_filter(HWND::default(), 0, 0, loop_callback)
+    }
+
+    /// Runs the Windows thread message loop, only retrieving messages matching the given filters.
+    ///
+    /// If a window is given, only messages for that window and its children are retrieved.
+    /// Only messages within the given range are retrieved, with `0..=0` retrieving all messages.
+    /// Quit messages are always retrieved.
+    ///
+    /// Otherwise behaves like [`Self::run_thread_message_loop`].
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the message loop is already running.
+    #[cfg(feature = "ui")]
+    pub fn run_filtered<F>(
+        window: Option<&WindowHandle>,
+        message_range: RangeInclusive<u32>,
+        loop_callback: F,
+    ) -> io::Result<()>
+    where
+        F: FnMut() -> io::Result<()>,
+    {
+        Self::run_with_filter(
+            window.map(HWND::from).unwrap_or_default(),
+            *message_range.start(),
+            *message_range.end(),
+            loop_callback,
+        )
+    }
+
+    fn run_with_filter<F>(
+        window: HWND,
+        message_filter_min: u32,
+        message_filter_max: u32,
+        mut loop_callback: F,
+    ) -> io::Result<()>
     where
         F: FnMut() -> io::Result<()>,
     {
@@ -49,7 +103,7 @@ impl ThreadMessageLoop {
         let mut msg: MSG = Default::default();
         loop {
             unsafe {
-                GetMessageW(&mut msg, None, 0, 0)
+                GetMessageW(&mut msg, window, message_filter_min, message_filter_max)
                     .if_eq_to_error(BOOL(-1), io::Error::last_os_error)?;
             }
             if msg.message == WM_QUIT {
@@ -88,3 +142,26 @@ impl ThreadMessageLoop {
         Self::RUNNING.with(|running| running.get())
     }
 }
+
+/// Notifies all top-level windows that a system-wide setting has changed.
+///
+/// The area names the changed setting, e.g. `"Environment"` after changing environment variables
+/// in the registry, or `"ImmersiveColorSet"` after theme changes.
+///
+/// Windows that don't respond within 5 seconds or are hung are skipped.
+pub fn broadcast_setting_change(area: &str) -> io::Result<()> {
+    const TIMEOUT_MS: u32 = 5000;
+    let area = area.to_wide_string();
+    let result = unsafe {
+        SendMessageTimeoutW(
+            HWND_BROADCAST,
+            WM_SETTINGCHANGE,
+            WPARAM(0),
+            LPARAM(area.as_ptr() as isize),
+            SMTO_ABORTIFHUNG,
+            TIMEOUT_MS,
+            None,
+        )
+    };
+    result.if_null_get_last_error_else_drop()
+}
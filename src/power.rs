@@ -0,0 +1,256 @@
+//! Power management.
+
+use std::ffi::OsStr;
+use std::io;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use windows::core::PWSTR;
+use windows::Win32::Foundation::HANDLE;
+use windows::Win32::System::Power::{
+    GetSystemPowerStatus,
+    PowerClearRequest,
+    PowerCreateRequest,
+    PowerRequestDisplayRequired,
+    PowerRequestExecutionRequired,
+    PowerRequestSystemRequired,
+    PowerSetRequest,
+    SetThreadExecutionState,
+    ES_CONTINUOUS,
+    ES_DISPLAY_REQUIRED,
+    ES_SYSTEM_REQUIRED,
+    EXECUTION_STATE,
+    POWER_REQUEST_TYPE,
+    SYSTEM_POWER_STATUS,
+};
+use windows::Win32::System::SystemServices::POWER_REQUEST_CONTEXT_VERSION;
+use windows::Win32::System::Threading::{
+    POWER_REQUEST_CONTEXT_SIMPLE_STRING,
+    REASON_CONTEXT,
+    REASON_CONTEXT_0,
+};
+
+use crate::internal::AutoClose;
+use crate::string::ZeroTerminatedWideString;
+
+/// What to keep from going idle with [`KeepAwake`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum KeepAwakeMode {
+    /// Prevents the system from going to sleep, but allows the display to turn off.
+    System,
+    /// Keeps the display on.
+    Display,
+    /// Keeps both the system and the display on.
+    SystemAndDisplay,
+}
+
+impl From<KeepAwakeMode> for EXECUTION_STATE {
+    fn from(value: KeepAwakeMode) -> Self {
+        match value {
+            KeepAwakeMode::System => ES_SYSTEM_REQUIRED,
+            KeepAwakeMode::Display => ES_DISPLAY_REQUIRED,
+            KeepAwakeMode::SystemAndDisplay => ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED,
+        }
+    }
+}
+
+/// Prevents the system from going to sleep or turning off the display while in scope.
+///
+/// The requirement is tied to the current thread, which is why this type is not [`Send`].
+/// Creating a new instance on the same thread replaces the requirement of the previous one,
+/// dropping any instance clears it.
+#[derive(Debug)]
+pub struct KeepAwake {
+    phantom: PhantomData<*mut ()>,
+}
+
+impl KeepAwake {
+    pub fn new(mode: KeepAwakeMode) -> io::Result<Self> {
+        set_thread_execution_state(ES_CONTINUOUS | EXECUTION_STATE::from(mode))?;
+        Ok(Self {
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl Drop for KeepAwake {
+    fn drop(&mut self) {
+        let _ = set_thread_execution_state(ES_CONTINUOUS);
+    }
+}
+
+fn set_thread_execution_state(state: EXECUTION_STATE) -> io::Result<()> {
+    let previous_state = unsafe { SetThreadExecutionState(state) };
+    if previous_state.0 == 0 {
+        Err(io::ErrorKind::Other.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// What to keep active with a [`PowerRequest`].
+#[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
+pub struct PowerRequirements {
+    /// Keeps the display on.
+    pub display: bool,
+    /// Prevents the system from going to sleep automatically.
+    pub system: bool,
+    /// Keeps the process running instead of it being suspended, e.g. for UWP apps.
+    pub execution: bool,
+}
+
+impl PowerRequirements {
+    fn get_request_types(self) -> impl Iterator<Item = POWER_REQUEST_TYPE> {
+        [
+            (self.display, PowerRequestDisplayRequired),
+            (self.system, PowerRequestSystemRequired),
+            (self.execution, PowerRequestExecutionRequired),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, request_type)| enabled.then_some(request_type))
+    }
+}
+
+/// A power request keeping the display or system active while in scope.
+///
+/// In contrast to [`KeepAwake`], the request is not tied to a thread and carries a reason
+/// that is shown by `powercfg /requests`. Dropping the instance clears the request.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winapi_easy::power::{
+///     PowerRequest,
+///     PowerRequirements,
+/// };
+///
+/// let requirements = PowerRequirements {
+///     display: true,
+///     system: true,
+///     ..Default::default()
+/// };
+/// let request = PowerRequest::new("Playing video", requirements)?;
+/// // Play the video...
+/// drop(request);
+///
+/// # Result::<(), std::io::Error>::Ok(())
+/// ```
+pub struct PowerRequest {
+    handle: AutoClose<HANDLE>,
+    requirements: PowerRequirements,
+}
+
+impl PowerRequest {
+    pub fn new(reason: &str, requirements: PowerRequirements) -> io::Result<Self> {
+        let mut reason = ZeroTerminatedWideString::from_os_str(OsStr::new(reason));
+        let context = REASON_CONTEXT {
+            Version: POWER_REQUEST_CONTEXT_VERSION,
+            Flags: POWER_REQUEST_CONTEXT_SIMPLE_STRING,
+            Reason: REASON_CONTEXT_0 {
+                SimpleReasonString: PWSTR(reason.0.as_mut_ptr()),
+            },
+        };
+        let handle = unsafe { PowerCreateRequest(&context)? };
+        let request = Self {
+            handle: handle.into(),
+            requirements,
+        };
+        for request_type in requirements.get_request_types() {
+            unsafe { PowerSetRequest(request.handle.entity, request_type)? };
+        }
+        Ok(request)
+    }
+}
+
+impl Drop for PowerRequest {
+    fn drop(&mut self) {
+        for request_type in self.requirements.get_request_types() {
+            let _ = unsafe { PowerClearRequest(self.handle.entity, request_type) };
+        }
+    }
+}
+
+/// The system power status, see [`get_system_power_status`].
+///
+/// Values are `None` if unknown.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct SystemPowerStatus {
+    /// Whether the system is connected to AC power.
+    pub ac_power: Option<bool>,
+    /// Whether the system has a battery.
+    pub has_battery: Option<bool>,
+    /// Remaining battery charge in percent.
+    pub battery_percent: Option<u8>,
+    /// Remaining battery time.
+    pub battery_time_remaining: Option<Duration>,
+    /// Whether the battery saver is on.
+    pub battery_saver_on: bool,
+}
+
+impl From<SYSTEM_POWER_STATUS> for SystemPowerStatus {
+    fn from(value: SYSTEM_POWER_STATUS) -> Self {
+        const UNKNOWN: u8 = 255;
+        const NO_SYSTEM_BATTERY_FLAG: u8 = 128;
+        Self {
+            ac_power: match value.ACLineStatus {
+                0 => Some(false),
+                1 => Some(true),
+                _ => None,
+            },
+            has_battery: match value.BatteryFlag {
+                UNKNOWN => None,
+                flags => Some(flags & NO_SYSTEM_BATTERY_FLAG == 0),
+            },
+            battery_percent: match value.BatteryLifePercent {
+                UNKNOWN => None,
+                percent => Some(percent),
+            },
+            battery_time_remaining: match value.BatteryLifeTime {
+                u32::MAX => None,
+                seconds => Some(Duration::from_secs(seconds.into())),
+            },
+            battery_saver_on: value.SystemStatusFlag == 1,
+        }
+    }
+}
+
+/// Returns the current system power status, e.g. the battery charge.
+///
+/// Use `WindowMessageListener::handle_power_event` to get notified of changes.
+pub fn get_system_power_status() -> io::Result<SystemPowerStatus> {
+    let mut raw_status = SYSTEM_POWER_STATUS::default();
+    unsafe { GetSystemPowerStatus(&mut raw_status)? };
+    Ok(raw_status.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keep_awake() -> io::Result<()> {
+        let keep_awake = KeepAwake::new(KeepAwakeMode::SystemAndDisplay)?;
+        drop(keep_awake);
+        Ok(())
+    }
+
+    #[test]
+    fn power_request() -> io::Result<()> {
+        let requirements = PowerRequirements {
+            system: true,
+            ..Default::default()
+        };
+        let request = PowerRequest::new("Running tests", requirements)?;
+        drop(request);
+        Ok(())
+    }
+
+    #[test]
+    fn check_system_power_status() -> io::Result<()> {
+        let status = get_system_power_status()?;
+        if let Some(battery_percent) = status.battery_percent {
+            assert!(battery_percent <= 100);
+        }
+        Ok(())
+    }
+}
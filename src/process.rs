@@ -1,25 +1,103 @@
 //! Processes, threads.
 
-use std::ffi::c_void;
+use std::collections::BTreeMap;
+use std::ffi::{
+    c_void,
+    CStr,
+    OsStr,
+    OsString,
+};
 use std::io;
+use std::io::{
+    Read,
+    Write,
+};
 use std::mem;
+use std::os::windows::ffi::OsStrExt;
+use std::path::{
+    Path,
+    PathBuf,
+};
+use std::time::{
+    Duration,
+    SystemTime,
+    UNIX_EPOCH,
+};
+use std::{
+    ptr,
+    slice,
+};
 
 use ntapi::ntpsapi::{
     NtSetInformationProcess,
+    ProcessCommandLineInformation,
     ProcessIoPriority,
 };
 use num_enum::{
     IntoPrimitive,
     TryFromPrimitive,
 };
+use windows::core::{
+    w,
+    PCWSTR,
+    PWSTR,
+};
 use windows::Wdk::System::Threading::{
     NtQueryInformationProcess,
     PROCESSINFOCLASS,
 };
 use windows::Win32::Foundation::{
+    DuplicateHandle,
+    GetLastError,
+    LocalFree,
+    SetHandleInformation,
+    DUPLICATE_SAME_ACCESS,
+    ERROR_ALREADY_EXISTS,
+    ERROR_BROKEN_PIPE,
+    ERROR_INSUFFICIENT_BUFFER,
+    FILETIME,
     HANDLE,
+    HANDLE_FLAG_INHERIT,
+    HINSTANCE,
+    HLOCAL,
     HMODULE,
+    MAX_PATH,
+    UNICODE_STRING,
+};
+use windows::Win32::Security::{
+    GetSidSubAuthority,
+    GetSidSubAuthorityCount,
+    GetTokenInformation,
+    TokenElevation,
+    TokenElevationType,
+    TokenElevationTypeLimited,
+    TokenIntegrityLevel,
+    TOKEN_ELEVATION,
+    TOKEN_ELEVATION_TYPE,
+    TOKEN_INFORMATION_CLASS,
+    TOKEN_MANDATORY_LABEL,
+    TOKEN_QUERY,
 };
+use windows::Win32::Storage::FileSystem::{
+    GetFileVersionInfoSizeW,
+    GetFileVersionInfoW,
+    ReadFile,
+    VerQueryValueW,
+    WriteFile,
+    VS_FIXEDFILEINFO,
+};
+use windows::Win32::System::Console::{
+    GetStdHandle,
+    STD_ERROR_HANDLE,
+    STD_HANDLE,
+    STD_INPUT_HANDLE,
+    STD_OUTPUT_HANDLE,
+};
+use windows::Win32::System::Diagnostics::Debug::IMAGE_DIRECTORY_ENTRY_EXPORT;
+#[cfg(target_pointer_width = "32")]
+use windows::Win32::System::Diagnostics::Debug::IMAGE_NT_HEADERS32;
+#[cfg(target_pointer_width = "64")]
+use windows::Win32::System::Diagnostics::Debug::IMAGE_NT_HEADERS64;
 use windows::Win32::System::Diagnostics::ToolHelp::{
     CreateToolhelp32Snapshot,
     Thread32First,
@@ -27,34 +105,112 @@ use windows::Win32::System::Diagnostics::ToolHelp::{
     TH32CS_SNAPTHREAD,
     THREADENTRY32,
 };
-use windows::Win32::System::LibraryLoader::GetModuleHandleExW;
+use windows::Win32::System::JobObjects::{
+    AssignProcessToJobObject,
+    CreateJobObjectW,
+    JobObjectExtendedLimitInformation,
+    QueryInformationJobObject,
+    SetInformationJobObject,
+    JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+    JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+};
+use windows::Win32::System::LibraryLoader::{
+    GetModuleFileNameW,
+    GetModuleHandleExW,
+};
+use windows::Win32::System::Pipes::CreatePipe;
+use windows::Win32::System::SystemServices::{
+    IMAGE_DOS_HEADER,
+    IMAGE_DOS_SIGNATURE,
+    IMAGE_EXPORT_DIRECTORY,
+    IMAGE_NT_SIGNATURE,
+    SECURITY_MANDATORY_HIGH_RID,
+    SECURITY_MANDATORY_LOW_RID,
+    SECURITY_MANDATORY_MEDIUM_RID,
+    SECURITY_MANDATORY_PROTECTED_PROCESS_RID,
+    SECURITY_MANDATORY_SYSTEM_RID,
+};
 use windows::Win32::System::Threading;
 use windows::Win32::System::Threading::{
+    CreateMutexW,
+    CreateProcessW,
+    CreateRemoteThreadEx,
+    DeleteProcThreadAttributeList,
     GetCurrentProcess,
     GetCurrentProcessId,
     GetCurrentThread,
     GetCurrentThreadId,
+    GetProcessAffinityMask,
     GetProcessId,
+    GetProcessTimes,
     GetThreadId,
+    GetThreadTimes,
+    InitializeProcThreadAttributeList,
     OpenProcess,
+    OpenProcessToken,
     OpenThread,
+    QueryFullProcessImageNameW,
+    ResumeThread,
     SetPriorityClass,
+    SetProcessAffinityMask,
+    SetThreadAffinityMask,
     SetThreadPriority,
+    SuspendThread,
+    UpdateProcThreadAttribute,
+    CREATE_NEW_CONSOLE,
+    CREATE_SUSPENDED,
+    CREATE_UNICODE_ENVIRONMENT,
+    EXTENDED_STARTUPINFO_PRESENT,
+    LPPROC_THREAD_ATTRIBUTE_LIST,
     PROCESS_ALL_ACCESS,
     PROCESS_CREATION_FLAGS,
+    PROCESS_INFORMATION,
     PROCESS_MODE_BACKGROUND_BEGIN,
     PROCESS_MODE_BACKGROUND_END,
+    PROCESS_NAME_WIN32,
+    PROCESS_QUERY_LIMITED_INFORMATION,
+    PROC_THREAD_ATTRIBUTE_HANDLE_LIST,
+    STARTF_USESTDHANDLES,
+    STARTUPINFOEXW,
+    STARTUPINFOW,
     THREAD_ALL_ACCESS,
     THREAD_MODE_BACKGROUND_BEGIN,
     THREAD_MODE_BACKGROUND_END,
     THREAD_PRIORITY,
 };
+use windows::Win32::UI::Shell::{
+    CommandLineToArgvW,
+    ShellExecuteExW,
+    SEE_MASK_NOASYNC,
+    SHELLEXECUTEINFOW,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    LoadStringW,
+    SW_SHOWNORMAL,
+};
 
+use crate::internal::windows_missing::{
+    HIWORD,
+    LOWORD,
+};
 use crate::internal::{
     custom_err_with_code,
     AutoClose,
+    CustomAutoDrop,
     ReturnValue,
 };
+use crate::messaging::broadcast_setting_change;
+use crate::registry::{
+    RegistryAccess,
+    RegistryHive,
+    RegistryKey,
+    RegistryValue,
+};
+use crate::string::{
+    FromWideString,
+    ToWideString,
+    ZeroTerminatedWideString,
+};
 
 /// A Windows process.
 pub struct Process {
@@ -83,6 +239,133 @@ impl Process {
         })
     }
 
+    /// Starts a new process.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use winapi_easy::process::{
+    ///     CommandSpec,
+    ///     Process,
+    ///     ProcessPriority,
+    /// };
+    ///
+    /// let process = Process::launch(
+    ///     &CommandSpec::new("notepad.exe")
+    ///         .arg("C:\\Some Folder\\file.txt")
+    ///         .priority(ProcessPriority::BelowNormal),
+    /// )?;
+    ///
+    /// # Result::<(), std::io::Error>::Ok(())
+    /// ```
+    pub fn launch(command: &CommandSpec) -> io::Result<Self> {
+        Self::launch_with_details(command).map(|launched| launched.process)
+    }
+
+    /// Starts a new process, also returning its primary thread and any requested standard I/O pipes.
+    ///
+    /// The thread is needed to start a process created with [`CommandSpec::start_suspended`]
+    /// using [`Thread::resume`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::io::Read;
+    ///
+    /// use winapi_easy::process::{
+    ///     CommandSpec,
+    ///     Process,
+    /// };
+    ///
+    /// let launched = Process::launch_with_details(
+    ///     &CommandSpec::new("cmd.exe")
+    ///         .args(["/c", "echo", "Hello"])
+    ///         .pipe_stdout(true),
+    /// )?;
+    /// let mut output = String::new();
+    /// launched.stdout.unwrap().read_to_string(&mut output)?;
+    ///
+    /// # Result::<(), std::io::Error>::Ok(())
+    /// ```
+    pub fn launch_with_details(command: &CommandSpec) -> io::Result<LaunchedProcess> {
+        let mut command_line = ZeroTerminatedWideString::from_os_str(command.get_command_line());
+        let environment_block = command.get_environment_block();
+        let working_directory = command
+            .working_directory
+            .as_ref()
+            .map(ZeroTerminatedWideString::from_os_str);
+        let mut creation_flags = CREATE_UNICODE_ENVIRONMENT;
+        if let Some(priority) = command.priority {
+            creation_flags |= priority.into();
+        }
+        if command.new_console {
+            creation_flags |= CREATE_NEW_CONSOLE;
+        }
+        if command.start_suspended {
+            creation_flags |= CREATE_SUSPENDED;
+        }
+        let mut startup_info = STARTUPINFOEXW {
+            StartupInfo: STARTUPINFOW {
+                cb: mem::size_of::<STARTUPINFOEXW>().try_into().unwrap(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let use_pipes = command.pipe_stdin || command.pipe_stdout || command.pipe_stderr;
+        let mut child_stdio = ChildStdio::default();
+        let mut attribute_list_buffer: Vec<u64> = Vec::new();
+        let mut _attribute_list_guard = None;
+        if use_pipes {
+            child_stdio = ChildStdio::new(command)?;
+            let inherited_handles = child_stdio.get_inherited_handles();
+            let attribute_list =
+                create_handle_list_attribute(&mut attribute_list_buffer, &inherited_handles)?;
+            _attribute_list_guard = Some(CustomAutoDrop {
+                value: attribute_list,
+                drop_fn: |attribute_list| unsafe { DeleteProcThreadAttributeList(*attribute_list) },
+            });
+            startup_info.StartupInfo.dwFlags |= STARTF_USESTDHANDLES;
+            startup_info.StartupInfo.hStdInput = child_stdio.get_child_handle(0);
+            startup_info.StartupInfo.hStdOutput = child_stdio.get_child_handle(1);
+            startup_info.StartupInfo.hStdError = child_stdio.get_child_handle(2);
+            startup_info.lpAttributeList = attribute_list;
+            creation_flags |= EXTENDED_STARTUPINFO_PRESENT;
+        }
+        let mut process_info = PROCESS_INFORMATION::default();
+        unsafe {
+            CreateProcessW(
+                PCWSTR::null(),
+                PWSTR(command_line.0.as_mut_ptr()),
+                None,
+                None,
+                // Only the handles in the attribute list will actually be inherited
+                use_pipes,
+                creation_flags,
+                environment_block
+                    .as_ref()
+                    .map(|block| block.as_ptr().cast::<c_void>()),
+                working_directory
+                    .as_ref()
+                    .map_or(PCWSTR::null(), ZeroTerminatedWideString::as_raw_pcwstr),
+                ptr::addr_of!(startup_info.StartupInfo),
+                &mut process_info,
+            )?;
+        }
+        let ChildStdio {
+            child_ends,
+            parent_ends: [stdin, stdout, stderr],
+        } = child_stdio;
+        // The child ends must be closed in this process, otherwise reading would never reach EOF
+        drop(child_ends);
+        Ok(LaunchedProcess {
+            process: Self::from_non_null(process_info.hProcess),
+            main_thread: Thread::from_non_null(process_info.hThread),
+            stdin: stdin.map(|handle| PipeWriter { handle }),
+            stdout: stdout.map(|handle| PipeReader { handle }),
+            stderr: stderr.map(|handle| PipeReader { handle }),
+        })
+    }
+
     /// Sets the current process to background processing mode.
     ///
     /// This will also lower the I/O priority of the process, which will lower the impact of heavy disk I/O on other processes.
@@ -113,6 +396,24 @@ impl Process {
         Ok(())
     }
 
+    /// Returns the set of processors the process is allowed to run on.
+    ///
+    /// Each bit of the mask represents a logical processor of the current processor group.
+    pub fn get_affinity_mask(&self) -> io::Result<usize> {
+        let mut process_mask: usize = 0;
+        let mut system_mask: usize = 0;
+        unsafe { GetProcessAffinityMask(self.handle.entity, &mut process_mask, &mut system_mask)? };
+        Ok(process_mask)
+    }
+
+    /// Restricts the process to run only on the given set of processors.
+    ///
+    /// The mask must be a subset of the processors available to the system, see [`Self::get_affinity_mask`].
+    pub fn set_affinity_mask(&mut self, mask: usize) -> io::Result<()> {
+        unsafe { SetProcessAffinityMask(self.handle.entity, mask)? };
+        Ok(())
+    }
+
     /// Returns the I/O priority of the process.
     ///
     /// Will return `None` if it is an unknown value.
@@ -134,6 +435,109 @@ impl Process {
         Ok(IoPriority::try_from(raw_io_priority as u32).ok())
     }
 
+    /// Returns the command line arguments the process was started with.
+    ///
+    /// The first element is usually the executable path. The command line is split into arguments
+    /// the same way as done by the standard C runtime startup code.
+    ///
+    /// This also works for processes with a different bitness than the current one.
+    ///
+    /// Requires Windows 8.1 or later, since the command line is queried directly from the system
+    /// instead of reading it from the process memory.
+    pub fn get_command_line(&self) -> io::Result<Vec<String>> {
+        let information_class: PROCESSINFOCLASS =
+            ProcessInformationClass::ProcessCommandLineInformation.into();
+        let mut return_length: u32 = 0;
+        // First call only determines the required buffer size
+        let _ = unsafe {
+            NtQueryInformationProcess(
+                self.handle.entity,
+                information_class,
+                ptr::null_mut(),
+                0,
+                &mut return_length,
+            )
+        };
+        return_length.if_null_to_error(|| {
+            io::Error::new(
+                io::ErrorKind::PermissionDenied,
+                "Cannot determine command line length",
+            )
+        })?;
+        // Using u64 elements to satisfy the alignment of the `UNICODE_STRING` header
+        let mut buffer: Vec<u64> =
+            vec![0; (return_length as usize).div_ceil(mem::size_of::<u64>())];
+        let ret_val = unsafe {
+            NtQueryInformationProcess(
+                self.handle.entity,
+                information_class,
+                buffer.as_mut_ptr().cast::<c_void>(),
+                (buffer.len() * mem::size_of::<u64>()) as u32,
+                &mut return_length,
+            )
+        };
+        ret_val.0.if_non_null_to_error(|| {
+            custom_err_with_code("Getting command line failed", ret_val.0)
+        })?;
+        // The string data is located in the buffer directly after the header
+        let command_line: Vec<u16> = unsafe {
+            let unicode_string = &*(buffer.as_ptr().cast::<UNICODE_STRING>());
+            let length = usize::from(unicode_string.Length) / mem::size_of::<u16>();
+            if length == 0 {
+                return Ok(Vec::new());
+            }
+            slice::from_raw_parts(unicode_string.Buffer.0, length).to_vec()
+        };
+        split_command_line(&command_line.to_os_string())
+    }
+
+    /// Creates a new thread in the process, running the function at the given address.
+    ///
+    /// If `start_suspended` is `true`, the thread will not run until [`Thread::resume`] is called.
+    ///
+    /// # Safety
+    ///
+    /// The start address must point to a function with a matching signature in the address space of the process,
+    /// and the parameter must be valid for that function in that process.
+    pub unsafe fn create_remote_thread(
+        &self,
+        start_address: unsafe extern "system" fn(*mut c_void) -> u32,
+        parameter: Option<*const c_void>,
+        start_suspended: bool,
+    ) -> io::Result<Thread> {
+        let creation_flags = if start_suspended {
+            CREATE_SUSPENDED.0
+        } else {
+            0
+        };
+        let raw_handle = CreateRemoteThreadEx(
+            self.handle.entity,
+            None,
+            0,
+            Some(start_address),
+            parameter,
+            creation_flags,
+            None,
+            None,
+        )?;
+        Ok(Thread::from_non_null(raw_handle))
+    }
+
+    /// Returns the process creation time and the CPU time used by all of its threads.
+    pub fn get_cpu_times(&self) -> io::Result<CpuTimes> {
+        let mut raw_times = RawCpuTimes::default();
+        unsafe {
+            GetProcessTimes(
+                self.handle.entity,
+                &mut raw_times.creation_time,
+                &mut raw_times.exit_time,
+                &mut raw_times.kernel_time,
+                &mut raw_times.user_time,
+            )?;
+        }
+        Ok(raw_times.into())
+    }
+
     pub fn set_io_priority(&mut self, io_priority: IoPriority) -> io::Result<()> {
         let ret_val = unsafe {
             NtSetInformationProcess(
@@ -155,7 +559,6 @@ impl Process {
         ProcessId(id)
     }
 
-    #[allow(dead_code)]
     fn from_non_null(handle: HANDLE) -> Self {
         Self {
             handle: handle.into(),
@@ -181,6 +584,362 @@ impl TryFrom<ProcessId> for Process {
     }
 }
 
+/// A process started by [`Process::launch_with_details`].
+pub struct LaunchedProcess {
+    pub process: Process,
+    pub main_thread: Thread,
+    /// Pipe to the standard input of the process if [`CommandSpec::pipe_stdin`] was used.
+    ///
+    /// Drop it to signal EOF to the process.
+    pub stdin: Option<PipeWriter>,
+    /// Pipe from the standard output of the process if [`CommandSpec::pipe_stdout`] was used.
+    pub stdout: Option<PipeReader>,
+    /// Pipe from the standard error of the process if [`CommandSpec::pipe_stderr`] was used.
+    pub stderr: Option<PipeReader>,
+}
+
+/// Reading end of an anonymous pipe.
+pub struct PipeReader {
+    handle: AutoClose<HANDLE>,
+}
+
+impl Read for PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let max_len = buf.len().min(u32::MAX as usize);
+        let mut bytes_read: u32 = 0;
+        let result = unsafe {
+            ReadFile(
+                self.handle.entity,
+                Some(&mut buf[..max_len]),
+                Some(&mut bytes_read),
+                None,
+            )
+        };
+        match result {
+            Ok(()) => Ok(bytes_read as usize),
+            // The writing end was closed
+            Err(err) if err.code() == ERROR_BROKEN_PIPE.to_hresult() => Ok(0),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// Writing end of an anonymous pipe.
+pub struct PipeWriter {
+    handle: AutoClose<HANDLE>,
+}
+
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let max_len = buf.len().min(u32::MAX as usize);
+        let mut bytes_written: u32 = 0;
+        unsafe {
+            WriteFile(
+                self.handle.entity,
+                Some(&buf[..max_len]),
+                Some(&mut bytes_written),
+                None,
+            )?;
+        }
+        Ok(bytes_written as usize)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Standard I/O handles for a new process, in the order stdin, stdout, stderr.
+#[derive(Default)]
+struct ChildStdio {
+    /// Inheritable handles for the child, either pipe ends or duplicates of the current standard handles.
+    child_ends: [Option<AutoClose<HANDLE>>; 3],
+    parent_ends: [Option<AutoClose<HANDLE>>; 3],
+}
+
+impl ChildStdio {
+    fn new(command: &CommandSpec) -> io::Result<Self> {
+        let mut result = Self::default();
+        let streams = [
+            (command.pipe_stdin, STD_INPUT_HANDLE),
+            (command.pipe_stdout, STD_OUTPUT_HANDLE),
+            (command.pipe_stderr, STD_ERROR_HANDLE),
+        ];
+        for (index, (use_pipe, std_handle)) in streams.into_iter().enumerate() {
+            if use_pipe {
+                let (read_end, write_end) = create_pipe()?;
+                let (child_end, parent_end) = if std_handle == STD_INPUT_HANDLE {
+                    (read_end, write_end)
+                } else {
+                    (write_end, read_end)
+                };
+                unsafe {
+                    SetHandleInformation(
+                        child_end.entity,
+                        HANDLE_FLAG_INHERIT.0,
+                        HANDLE_FLAG_INHERIT,
+                    )?;
+                }
+                result.child_ends[index] = Some(child_end);
+                result.parent_ends[index] = Some(parent_end);
+            } else {
+                result.child_ends[index] = duplicate_std_handle_inheritable(std_handle)?;
+            }
+        }
+        Ok(result)
+    }
+
+    fn get_child_handle(&self, index: usize) -> HANDLE {
+        self.child_ends[index]
+            .as_ref()
+            .map_or(HANDLE::default(), |handle| handle.entity)
+    }
+
+    fn get_inherited_handles(&self) -> Vec<HANDLE> {
+        self.child_ends
+            .iter()
+            .flatten()
+            .map(|handle| handle.entity)
+            .collect()
+    }
+}
+
+/// Creates a non-inheritable anonymous pipe, returning the read and write ends.
+fn create_pipe() -> io::Result<(AutoClose<HANDLE>, AutoClose<HANDLE>)> {
+    let mut read_end = HANDLE::default();
+    let mut write_end = HANDLE::default();
+    unsafe { CreatePipe(&mut read_end, &mut write_end, None, 0)? };
+    Ok((read_end.into(), write_end.into()))
+}
+
+fn duplicate_std_handle_inheritable(
+    std_handle: STD_HANDLE,
+) -> io::Result<Option<AutoClose<HANDLE>>> {
+    let Ok(handle) = (unsafe { GetStdHandle(std_handle) }) else {
+        return Ok(None);
+    };
+    if handle.is_null() {
+        return Ok(None);
+    }
+    let current_process = unsafe { GetCurrentProcess() };
+    let mut duplicate = HANDLE::default();
+    unsafe {
+        DuplicateHandle(
+            current_process,
+            handle,
+            current_process,
+            &mut duplicate,
+            0,
+            true,
+            DUPLICATE_SAME_ACCESS,
+        )?;
+    }
+    Ok(Some(duplicate.into()))
+}
+
+/// Creates an attribute list restricting handle inheritance to the given handles.
+///
+/// This prevents unrelated inheritable handles of the current process from leaking into the child.
+fn create_handle_list_attribute(
+    buffer: &mut Vec<u64>,
+    handles: &[HANDLE],
+) -> io::Result<LPPROC_THREAD_ATTRIBUTE_LIST> {
+    let mut size: usize = 0;
+    // First call only determines the required buffer size
+    let _ = unsafe {
+        InitializeProcThreadAttributeList(
+            LPPROC_THREAD_ATTRIBUTE_LIST(ptr::null_mut()),
+            1,
+            0,
+            &mut size,
+        )
+    };
+    *buffer = vec![0; size.div_ceil(mem::size_of::<u64>())];
+    let attribute_list = LPPROC_THREAD_ATTRIBUTE_LIST(buffer.as_mut_ptr().cast::<c_void>());
+    unsafe {
+        InitializeProcThreadAttributeList(attribute_list, 1, 0, &mut size)?;
+        let update_result = UpdateProcThreadAttribute(
+            attribute_list,
+            0,
+            PROC_THREAD_ATTRIBUTE_HANDLE_LIST as usize,
+            Some(handles.as_ptr().cast::<c_void>()),
+            mem::size_of_val(handles),
+            None,
+            None,
+        );
+        if let Err(err) = update_result {
+            DeleteProcThreadAttributeList(attribute_list);
+            return Err(err.into());
+        }
+    }
+    Ok(attribute_list)
+}
+
+/// Specification of a new process for [`Process::launch`].
+///
+/// The program is searched for in the same locations as by `CreateProcessW`,
+/// including the directories in the `PATH` environment variable.
+#[derive(Clone, Debug)]
+pub struct CommandSpec {
+    program: String,
+    args: Vec<String>,
+    working_directory: Option<PathBuf>,
+    clear_env: bool,
+    env_changes: Vec<(OsString, Option<OsString>)>,
+    priority: Option<ProcessPriority>,
+    new_console: bool,
+    start_suspended: bool,
+    pipe_stdin: bool,
+    pipe_stdout: bool,
+    pipe_stderr: bool,
+}
+
+impl CommandSpec {
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+            working_directory: None,
+            clear_env: false,
+            env_changes: Vec::new(),
+            priority: None,
+            new_console: false,
+            start_suspended: false,
+            pipe_stdin: false,
+            pipe_stdout: false,
+            pipe_stderr: false,
+        }
+    }
+
+    /// Adds an argument, quoting it as necessary.
+    pub fn arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Adds multiple arguments, quoting them as necessary.
+    pub fn args<I>(mut self, args: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Sets the working directory. By default, the current directory is inherited.
+    pub fn working_directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.working_directory = Some(directory.into());
+        self
+    }
+
+    /// Sets an environment variable for the new process.
+    pub fn env(mut self, name: impl Into<OsString>, value: impl Into<OsString>) -> Self {
+        self.env_changes.push((name.into(), Some(value.into())));
+        self
+    }
+
+    /// Removes an inherited environment variable for the new process.
+    pub fn env_remove(mut self, name: impl Into<OsString>) -> Self {
+        self.env_changes.push((name.into(), None));
+        self
+    }
+
+    /// Clears all inherited environment variables, including ones set before calling this.
+    pub fn env_clear(mut self) -> Self {
+        self.clear_env = true;
+        self.env_changes.clear();
+        self
+    }
+
+    /// Sets the CPU priority of the new process.
+    pub fn priority(mut self, priority: ProcessPriority) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    /// Whether a console program gets its own new console instead of inheriting the current one.
+    pub fn new_console(mut self, new_console: bool) -> Self {
+        self.new_console = new_console;
+        self
+    }
+
+    /// Whether the primary thread of the process is created in a suspended state.
+    pub fn start_suspended(mut self, start_suspended: bool) -> Self {
+        self.start_suspended = start_suspended;
+        self
+    }
+
+    /// Whether to connect the standard input of the process to a pipe.
+    ///
+    /// See [`LaunchedProcess::stdin`].
+    pub fn pipe_stdin(mut self, pipe_stdin: bool) -> Self {
+        self.pipe_stdin = pipe_stdin;
+        self
+    }
+
+    /// Whether to connect the standard output of the process to a pipe.
+    ///
+    /// See [`LaunchedProcess::stdout`].
+    pub fn pipe_stdout(mut self, pipe_stdout: bool) -> Self {
+        self.pipe_stdout = pipe_stdout;
+        self
+    }
+
+    /// Whether to connect the standard error of the process to a pipe.
+    ///
+    /// See [`LaunchedProcess::stderr`].
+    pub fn pipe_stderr(mut self, pipe_stderr: bool) -> Self {
+        self.pipe_stderr = pipe_stderr;
+        self
+    }
+
+    fn get_command_line(&self) -> String {
+        std::iter::once(&self.program)
+            .chain(&self.args)
+            .map(|arg| quote_command_line_arg(arg))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Returns the environment block, or `None` if the environment should be inherited unchanged.
+    fn get_environment_block(&self) -> Option<Vec<u16>> {
+        if !self.clear_env && self.env_changes.is_empty() {
+            return None;
+        }
+        // Names are case-insensitive and the block should be sorted by name
+        let mut variables: BTreeMap<OsString, (OsString, OsString)> = if self.clear_env {
+            BTreeMap::new()
+        } else {
+            std::env::vars_os()
+                .map(|(name, value)| (name.to_ascii_uppercase(), (name, value)))
+                .collect()
+        };
+        for (name, value) in &self.env_changes {
+            match value {
+                Some(value) => {
+                    variables.insert(name.to_ascii_uppercase(), (name.clone(), value.clone()));
+                }
+                None => {
+                    variables.remove(&name.to_ascii_uppercase());
+                }
+            }
+        }
+        let mut block: Vec<u16> = Vec::new();
+        for (name, value) in variables.values() {
+            block.extend(name.encode_wide());
+            block.push(u16::from(b'='));
+            block.extend(value.encode_wide());
+            block.push(0);
+        }
+        if block.is_empty() {
+            block.push(0);
+        }
+        block.push(0);
+        Some(block)
+    }
+}
+
 /// ID of a [`Process`].
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub struct ProcessId(pub(crate) u32);
@@ -190,6 +949,110 @@ impl ProcessId {
     pub fn current() -> Self {
         Self(unsafe { GetCurrentProcessId() })
     }
+
+    /// Returns the integrity level of the process.
+    ///
+    /// Input sent to windows of processes with a higher integrity level than the current one is silently discarded.
+    pub fn get_integrity_level(&self) -> io::Result<IntegrityLevel> {
+        let raw_handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, self.0)? };
+        let handle = AutoClose::from(raw_handle);
+        let mut raw_token = HANDLE::default();
+        unsafe { OpenProcessToken(handle.entity, TOKEN_QUERY, &mut raw_token)? };
+        let token = AutoClose::from(raw_token);
+        let mut required_size: u32 = 0;
+        // Expected to fail with `ERROR_INSUFFICIENT_BUFFER`
+        let _ = unsafe {
+            GetTokenInformation(
+                token.entity,
+                TokenIntegrityLevel,
+                None,
+                0,
+                &mut required_size,
+            )
+        };
+        // Using `u64` for sufficient alignment of the contained pointer
+        let mut buffer: Vec<u64> =
+            vec![0; (required_size as usize).div_ceil(mem::size_of::<u64>())];
+        unsafe {
+            GetTokenInformation(
+                token.entity,
+                TokenIntegrityLevel,
+                Some(buffer.as_mut_ptr().cast::<c_void>()),
+                required_size,
+                &mut required_size,
+            )?;
+        }
+        let rid = unsafe {
+            let label = &*buffer.as_ptr().cast::<TOKEN_MANDATORY_LABEL>();
+            let sub_authority_count = *GetSidSubAuthorityCount(label.Label.Sid);
+            if sub_authority_count == 0 {
+                return Err(io::ErrorKind::InvalidData.into());
+            }
+            *GetSidSubAuthority(label.Label.Sid, u32::from(sub_authority_count) - 1)
+        };
+        Ok(IntegrityLevel::from_rid(rid))
+    }
+
+    /// Returns the full path of the process's executable file.
+    ///
+    /// Other than [`Process::from_id`], this only requires limited query access to the process
+    /// and therefore also works for most processes of other users.
+    pub fn get_executable_path(&self) -> io::Result<PathBuf> {
+        let raw_handle = unsafe { OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, self.0)? };
+        let handle = AutoClose::from(raw_handle);
+        let mut buffer: Vec<u16> = vec![0; MAX_PATH as usize];
+        loop {
+            let mut size: u32 = buffer.len().try_into().unwrap();
+            let result = unsafe {
+                QueryFullProcessImageNameW(
+                    handle.entity,
+                    PROCESS_NAME_WIN32,
+                    PWSTR::from_raw(buffer.as_mut_ptr()),
+                    &mut size,
+                )
+            };
+            match result {
+                Ok(()) => {
+                    buffer.truncate(size as usize);
+                    return Ok(buffer.to_os_string().into());
+                }
+                Err(err) if err.code() == ERROR_INSUFFICIENT_BUFFER.to_hresult() => {
+                    buffer.resize(buffer.len() * 2, 0);
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+    }
+}
+
+/// The mandatory integrity level of a process, see [`ProcessId::get_integrity_level`].
+///
+/// Ordered from the lowest to the highest level.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub enum IntegrityLevel {
+    Untrusted,
+    /// Used for sandboxed processes, e.g. browser renderers.
+    Low,
+    /// The default level for processes of standard users and non-elevated administrators.
+    Medium,
+    /// The level for elevated processes.
+    High,
+    /// The level for system services.
+    System,
+    Protected,
+}
+
+impl IntegrityLevel {
+    fn from_rid(rid: u32) -> Self {
+        match rid as i32 {
+            rid if rid < SECURITY_MANDATORY_LOW_RID => Self::Untrusted,
+            rid if rid < SECURITY_MANDATORY_MEDIUM_RID => Self::Low,
+            rid if rid < SECURITY_MANDATORY_HIGH_RID => Self::Medium,
+            rid if rid < SECURITY_MANDATORY_SYSTEM_RID => Self::High,
+            rid if rid < SECURITY_MANDATORY_PROTECTED_PROCESS_RID => Self::System,
+            _ => Self::Protected,
+        }
+    }
 }
 
 /// A thread inside a Windows process.
@@ -249,12 +1112,52 @@ impl Thread {
         Ok(())
     }
 
+    /// Restricts the thread to run only on the given set of processors, returning the previous mask.
+    ///
+    /// The mask must be a subset of the processors the thread's process is allowed to run on,
+    /// see [`Process::get_affinity_mask`].
+    pub fn set_affinity_mask(&mut self, mask: usize) -> io::Result<usize> {
+        let previous_mask = unsafe { SetThreadAffinityMask(self.handle.entity, mask) };
+        previous_mask.if_null_get_last_error()
+    }
+
     pub fn get_id(&self) -> ThreadId {
         let id = unsafe { GetThreadId(self.handle.entity) };
         ThreadId(id)
     }
 
-    #[allow(dead_code)]
+    /// Suspends the thread.
+    ///
+    /// Suspensions are counted, the thread will only continue running if [`Self::resume`]
+    /// was called as many times as this function.
+    pub fn suspend(&self) -> io::Result<()> {
+        unsafe { SuspendThread(self.handle.entity) }
+            .if_eq_to_error(u32::MAX, io::Error::last_os_error)?;
+        Ok(())
+    }
+
+    /// Resumes a thread that was suspended or created in a suspended state.
+    pub fn resume(&self) -> io::Result<()> {
+        unsafe { ResumeThread(self.handle.entity) }
+            .if_eq_to_error(u32::MAX, io::Error::last_os_error)?;
+        Ok(())
+    }
+
+    /// Returns the thread creation time and the CPU time used by the thread.
+    pub fn get_cpu_times(&self) -> io::Result<CpuTimes> {
+        let mut raw_times = RawCpuTimes::default();
+        unsafe {
+            GetThreadTimes(
+                self.handle.entity,
+                &mut raw_times.creation_time,
+                &mut raw_times.exit_time,
+                &mut raw_times.kernel_time,
+                &mut raw_times.user_time,
+            )?;
+        }
+        Ok(raw_times.into())
+    }
+
     fn from_non_null(handle: HANDLE) -> Self {
         Self {
             handle: handle.into(),
@@ -408,6 +1311,7 @@ pub enum IoPriority {
 #[repr(i32)]
 enum ProcessInformationClass {
     ProcessIoPriority = ProcessIoPriority as i32,
+    ProcessCommandLineInformation = ProcessCommandLineInformation as i32,
 }
 
 impl From<ProcessInformationClass> for PROCESSINFOCLASS {
@@ -416,9 +1320,134 @@ impl From<ProcessInformationClass> for PROCESSINFOCLASS {
     }
 }
 
+/// Timing information of a [`Process`] or [`Thread`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct CpuTimes {
+    pub creation_time: SystemTime,
+    /// The exit time, or `None` if still running.
+    pub exit_time: Option<SystemTime>,
+    /// Time spent executing in kernel mode.
+    pub kernel_time: Duration,
+    /// Time spent executing in user mode.
+    pub user_time: Duration,
+}
+
+#[derive(Default)]
+struct RawCpuTimes {
+    creation_time: FILETIME,
+    exit_time: FILETIME,
+    kernel_time: FILETIME,
+    user_time: FILETIME,
+}
+
+impl From<RawCpuTimes> for CpuTimes {
+    fn from(value: RawCpuTimes) -> Self {
+        // Number of 100 ns intervals
+        fn to_intervals(file_time: FILETIME) -> u64 {
+            (u64::from(file_time.dwHighDateTime) << u32::BITS) | u64::from(file_time.dwLowDateTime)
+        }
+        fn to_duration(file_time: FILETIME) -> Duration {
+            Duration::from_nanos(to_intervals(file_time) * 100)
+        }
+        fn to_system_time(file_time: FILETIME) -> SystemTime {
+            // Difference between 1601-01-01 (Windows epoch) and 1970-01-01 in 100 ns intervals
+            const UNIX_EPOCH_INTERVALS: u64 = 116_444_736_000_000_000;
+            let intervals = to_intervals(file_time);
+            if intervals >= UNIX_EPOCH_INTERVALS {
+                UNIX_EPOCH + Duration::from_nanos((intervals - UNIX_EPOCH_INTERVALS) * 100)
+            } else {
+                UNIX_EPOCH - Duration::from_nanos((UNIX_EPOCH_INTERVALS - intervals) * 100)
+            }
+        }
+        Self {
+            creation_time: to_system_time(value.creation_time),
+            exit_time: (to_intervals(value.exit_time) != 0)
+                .then(|| to_system_time(value.exit_time)),
+            kernel_time: to_duration(value.kernel_time),
+            user_time: to_duration(value.user_time),
+        }
+    }
+}
+
+/// Scope of a persistent environment variable.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum EnvScope {
+    /// Variables of the current user.
+    User,
+    /// Variables for all users. Changing them requires administrator rights.
+    System,
+}
+
+impl EnvScope {
+    fn open_registry_key(self, access: RegistryAccess) -> io::Result<RegistryKey> {
+        let (hive, path) = match self {
+            EnvScope::User => (RegistryHive::CurrentUser, "Environment"),
+            EnvScope::System => (
+                RegistryHive::LocalMachine,
+                r"SYSTEM\CurrentControlSet\Control\Session Manager\Environment",
+            ),
+        };
+        RegistryKey::open(hive, path, access)
+    }
+}
+
+/// Returns the persisted value of an environment variable, if it is set.
+///
+/// This reads the variables as stored in the registry, not the environment of the current process.
+/// References to other variables like `%USERPROFILE%` are returned unexpanded.
+pub fn get_env_var(name: &str, scope: EnvScope) -> io::Result<Option<String>> {
+    let key = scope.open_registry_key(RegistryAccess::Read)?;
+    match key.get_value(name)? {
+        Some(RegistryValue::String(value) | RegistryValue::ExpandString(value)) => Ok(Some(value)),
+        Some(_) => Err(io::ErrorKind::InvalidData.into()),
+        None => Ok(None),
+    }
+}
+
+/// Persists an environment variable and notifies other applications of the change.
+///
+/// Values containing references to other variables like `%USERPROFILE%` are stored as expandable strings.
+/// The environment of the current process is not changed.
+///
+/// Setting a variable with [`EnvScope::System`] requires administrator rights.
+pub fn set_env_var(name: &str, value: &str, scope: EnvScope) -> io::Result<()> {
+    let value = if value.contains('%') {
+        RegistryValue::ExpandString(value.to_string())
+    } else {
+        RegistryValue::String(value.to_string())
+    };
+    let key = scope.open_registry_key(RegistryAccess::Write)?;
+    key.set_value(name, &value)?;
+    broadcast_setting_change("Environment")
+}
+
+fn split_command_line(command_line: &OsStr) -> io::Result<Vec<String>> {
+    let mut num_args: i32 = 0;
+    let raw_args = unsafe {
+        CommandLineToArgvW(
+            PCWSTR::from_raw(command_line.to_wide_string().as_ptr()),
+            &mut num_args,
+        )
+    };
+    if raw_args.is_null() {
+        return Err(io::Error::last_os_error());
+    }
+    let _free_guard = CustomAutoDrop {
+        value: raw_args,
+        drop_fn: |x| unsafe {
+            let _ = LocalFree(HLOCAL(x.cast::<c_void>()));
+        },
+    };
+    let args = unsafe { slice::from_raw_parts(raw_args, num_args.try_into().unwrap()) };
+    let result = args
+        .iter()
+        .map(|arg| unsafe { arg.as_wide().to_string_lossy() })
+        .collect();
+    Ok(result)
+}
+
 /// A handle to a module (EXE or DLL).
 pub struct ModuleHandle {
-    #[allow(dead_code)]
     raw_handle: HMODULE,
 }
 
@@ -432,6 +1461,419 @@ impl ModuleHandle {
         };
         Ok(ModuleHandle { raw_handle })
     }
+
+    /// Returns the handle of a module that is already loaded into the current process.
+    pub fn get_loaded(module_name: &str) -> io::Result<Self> {
+        let raw_handle = unsafe {
+            let mut h_module: HMODULE = Default::default();
+            GetModuleHandleExW(
+                0,
+                PCWSTR::from_raw(module_name.to_wide_string().as_ptr()),
+                &mut h_module,
+            )?;
+            h_module.if_null_get_last_error()?
+        };
+        Ok(ModuleHandle { raw_handle })
+    }
+
+    /// Returns the full path of the module's file.
+    pub fn get_path(&self) -> io::Result<PathBuf> {
+        let mut buffer: Vec<u16> = vec![0; MAX_PATH as usize];
+        loop {
+            let chars_copied = unsafe { GetModuleFileNameW(self.raw_handle, &mut buffer) };
+            chars_copied.if_null_get_last_error()?;
+            // The path is truncated if the buffer is too small
+            if (chars_copied as usize) < buffer.len() {
+                buffer.truncate(chars_copied as usize);
+                return Ok(buffer.to_os_string().into());
+            }
+            buffer.resize(buffer.len() * 2, 0);
+        }
+    }
+
+    /// Returns the version information from the module file's version resource.
+    pub fn get_version_info(&self) -> io::Result<VersionInfo> {
+        get_file_version_info(self.get_path()?)
+    }
+
+    /// Loads a string from the module's string table resource.
+    ///
+    /// Fails if the string doesn't exist or is empty.
+    pub fn load_string(&self, id: u32) -> io::Result<String> {
+        let mut string_ptr: *const u16 = ptr::null();
+        // With a buffer size of 0, a read-only pointer to the resource itself is returned
+        let length = unsafe {
+            LoadStringW(
+                HINSTANCE(self.raw_handle.0),
+                id,
+                PWSTR(ptr::addr_of_mut!(string_ptr).cast::<u16>()),
+                0,
+            )
+        }
+        .if_null_get_last_error()?;
+        let chars = unsafe { slice::from_raw_parts(string_ptr, length.try_into().unwrap()) };
+        Ok(chars.to_string_lossy())
+    }
+
+    /// Returns the names of all symbols exported by name from the module.
+    ///
+    /// Symbols exported only by ordinal are not included.
+    pub fn get_exported_symbols(&self) -> io::Result<Vec<String>> {
+        #[cfg(target_pointer_width = "64")]
+        type ImageNtHeaders = IMAGE_NT_HEADERS64;
+        #[cfg(target_pointer_width = "32")]
+        type ImageNtHeaders = IMAGE_NT_HEADERS32;
+
+        let invalid_data_err = || io::Error::new(io::ErrorKind::InvalidData, "Invalid PE image");
+
+        let base_address = self.raw_handle.0.cast::<u8>().cast_const();
+        // The module is mapped into the address space of this process as long as we hold the handle,
+        // and all offsets are relative to its base address.
+        unsafe {
+            let dos_header = &*base_address.cast::<IMAGE_DOS_HEADER>();
+            if dos_header.e_magic != IMAGE_DOS_SIGNATURE {
+                return Err(invalid_data_err());
+            }
+            let nt_headers = &*base_address
+                .offset(dos_header.e_lfanew.try_into().unwrap())
+                .cast::<ImageNtHeaders>();
+            if nt_headers.Signature != IMAGE_NT_SIGNATURE {
+                return Err(invalid_data_err());
+            }
+            let export_data_directory = nt_headers
+                .OptionalHeader
+                .DataDirectory
+                .get(usize::from(IMAGE_DIRECTORY_ENTRY_EXPORT.0))
+                .ok_or_else(invalid_data_err)?;
+            if export_data_directory.VirtualAddress == 0 || export_data_directory.Size == 0 {
+                return Ok(Vec::new());
+            }
+            let export_directory = &*base_address
+                .add(export_data_directory.VirtualAddress.try_into().unwrap())
+                .cast::<IMAGE_EXPORT_DIRECTORY>();
+            let name_addresses = slice::from_raw_parts(
+                base_address
+                    .add(export_directory.AddressOfNames.try_into().unwrap())
+                    .cast::<u32>(),
+                export_directory.NumberOfNames.try_into().unwrap(),
+            );
+            let result = name_addresses
+                .iter()
+                .map(|name_address| {
+                    let name_ptr = base_address.add((*name_address).try_into().unwrap());
+                    CStr::from_ptr(name_ptr.cast())
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect();
+            Ok(result)
+        }
+    }
+}
+
+/// A four-part version number as used in version resources.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
+pub struct Version {
+    pub major: u16,
+    pub minor: u16,
+    pub build: u16,
+    pub revision: u16,
+}
+
+impl Version {
+    fn from_raw(most_significant: u32, least_significant: u32) -> Self {
+        Self {
+            major: HIWORD(most_significant),
+            minor: LOWORD(most_significant),
+            build: HIWORD(least_significant),
+            revision: LOWORD(least_significant),
+        }
+    }
+}
+
+/// Version information of an executable file, see [`get_file_version_info`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct VersionInfo {
+    pub file_version: Version,
+    pub product_version: Version,
+}
+
+/// Returns the version information from the version resource of an executable file (EXE or DLL).
+///
+/// Will fail if the file has no version resource.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winapi_easy::process::get_file_version_info;
+///
+/// let version_info = get_file_version_info(r"C:\Windows\explorer.exe")?;
+/// println!("Explorer version: {:?}", version_info.file_version);
+///
+/// # Result::<(), std::io::Error>::Ok(())
+/// ```
+pub fn get_file_version_info<P: AsRef<Path>>(path: P) -> io::Result<VersionInfo> {
+    let wide_path = ZeroTerminatedWideString::from_os_str(path.as_ref());
+    let size = unsafe { GetFileVersionInfoSizeW(wide_path.as_raw_pcwstr(), None) }
+        .if_null_get_last_error()?;
+    let mut buffer: Vec<u8> = vec![0; size as usize];
+    unsafe {
+        GetFileVersionInfoW(
+            wide_path.as_raw_pcwstr(),
+            0,
+            size,
+            buffer.as_mut_ptr().cast::<c_void>(),
+        )?;
+    }
+    let mut fixed_info_ptr: *mut c_void = ptr::null_mut();
+    let mut fixed_info_size: u32 = 0;
+    unsafe {
+        VerQueryValueW(
+            buffer.as_ptr().cast::<c_void>(),
+            // Root block containing the `VS_FIXEDFILEINFO`
+            w!("\\"),
+            &mut fixed_info_ptr,
+            &mut fixed_info_size,
+        )
+    }
+    .if_null_to_error_else_drop(|| io::ErrorKind::InvalidData.into())?;
+    if fixed_info_ptr.is_null() || (fixed_info_size as usize) < mem::size_of::<VS_FIXEDFILEINFO>() {
+        return Err(io::ErrorKind::InvalidData.into());
+    }
+    // The pointer points into `buffer`, which is still alive
+    let fixed_info = unsafe { fixed_info_ptr.cast::<VS_FIXEDFILEINFO>().read_unaligned() };
+    Ok(VersionInfo {
+        file_version: Version::from_raw(fixed_info.dwFileVersionMS, fixed_info.dwFileVersionLS),
+        product_version: Version::from_raw(
+            fixed_info.dwProductVersionMS,
+            fixed_info.dwProductVersionLS,
+        ),
+    })
+}
+
+/// A guard ensuring that only one instance of an application is running at a time.
+///
+/// Backed by a named mutex which is released when the last guard holding it is dropped.
+pub struct SingleInstance {
+    #[allow(dead_code)]
+    handle: AutoClose<HANDLE>,
+}
+
+impl SingleInstance {
+    /// Tries to acquire the single instance guard with the given name.
+    ///
+    /// Returns `None` if another instance already holds a guard with the same name.
+    ///
+    /// The name is system-wide within the current session. To make it valid across all sessions,
+    /// use the `Global\` prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use winapi_easy::process::SingleInstance;
+    ///
+    /// let Some(_instance) = SingleInstance::acquire("MyApplication-SingleInstance")? else {
+    ///     println!("Application is already running");
+    ///     return Ok(());
+    /// };
+    ///
+    /// # Result::<(), std::io::Error>::Ok(())
+    /// ```
+    pub fn acquire(name: &str) -> io::Result<Option<Self>> {
+        let wide_name = ZeroTerminatedWideString::from_os_str(name);
+        let handle = unsafe { CreateMutexW(None, false, wide_name.as_raw_pcwstr())? };
+        // Must be queried before anything else can reset the last error
+        let already_exists = unsafe { GetLastError() } == ERROR_ALREADY_EXISTS;
+        let handle = AutoClose::from(handle);
+        if already_exists {
+            Ok(None)
+        } else {
+            Ok(Some(Self { handle }))
+        }
+    }
+}
+
+/// A job object, allowing a group of processes to be managed as a unit.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winapi_easy::process::{
+///     CommandSpec,
+///     JobObject,
+///     Process,
+/// };
+///
+/// let job = JobObject::new()?;
+/// job.set_kill_on_close()?;
+/// let child = Process::launch(&CommandSpec::new("notepad.exe"))?;
+/// job.assign(&child)?;
+/// // The child process is terminated when the job object is dropped
+/// drop(job);
+///
+/// # Result::<(), std::io::Error>::Ok(())
+/// ```
+pub struct JobObject {
+    handle: AutoClose<HANDLE>,
+}
+
+impl JobObject {
+    /// Creates a new anonymous job object.
+    pub fn new() -> io::Result<Self> {
+        let handle = unsafe { CreateJobObjectW(None, PCWSTR::null())? };
+        Ok(Self {
+            handle: handle.into(),
+        })
+    }
+
+    /// Adds a process to the job.
+    ///
+    /// Child processes created by the process afterwards will also be part of the job.
+    pub fn assign(&self, process: &Process) -> io::Result<()> {
+        unsafe { AssignProcessToJobObject(self.handle.entity, process.handle.entity)? };
+        Ok(())
+    }
+
+    /// Causes all processes of the job to be terminated when the last handle to the job is closed.
+    ///
+    /// This includes the case of the owning process exiting or crashing.
+    pub fn set_kill_on_close(&self) -> io::Result<()> {
+        let mut limit_info = self.get_extended_limit_info()?;
+        limit_info.BasicLimitInformation.LimitFlags |= JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+        unsafe {
+            SetInformationJobObject(
+                self.handle.entity,
+                JobObjectExtendedLimitInformation,
+                ptr::addr_of!(limit_info).cast::<c_void>(),
+                mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+            )?;
+        }
+        Ok(())
+    }
+
+    fn get_extended_limit_info(&self) -> io::Result<JOBOBJECT_EXTENDED_LIMIT_INFORMATION> {
+        let mut limit_info = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+        unsafe {
+            QueryInformationJobObject(
+                self.handle.entity,
+                JobObjectExtendedLimitInformation,
+                ptr::addr_of_mut!(limit_info).cast::<c_void>(),
+                mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+                None,
+            )?;
+        }
+        Ok(limit_info)
+    }
+}
+
+/// Returns `true` if the current process is running with elevated (administrator) rights.
+pub fn is_elevated() -> io::Result<bool> {
+    let elevation: TOKEN_ELEVATION = get_current_process_token_info(TokenElevation)?;
+    Ok(elevation.TokenIsElevated != 0)
+}
+
+/// Returns `true` if the current process is not elevated but could be elevated via UAC.
+///
+/// This is the case if the user is an administrator but the process runs with a filtered, limited token.
+pub fn is_elevation_available() -> io::Result<bool> {
+    let elevation_type: TOKEN_ELEVATION_TYPE = get_current_process_token_info(TokenElevationType)?;
+    Ok(elevation_type == TokenElevationTypeLimited)
+}
+
+/// Queries fixed size token information of the current process.
+fn get_current_process_token_info<T: Default>(
+    info_class: TOKEN_INFORMATION_CLASS,
+) -> io::Result<T> {
+    let mut raw_token = HANDLE::default();
+    unsafe { OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut raw_token)? };
+    let token = AutoClose::from(raw_token);
+    let mut result = T::default();
+    let mut return_length: u32 = 0;
+    unsafe {
+        GetTokenInformation(
+            token.entity,
+            info_class,
+            Some(&mut result as *mut T as *mut c_void),
+            mem::size_of::<T>().try_into().unwrap(),
+            &mut return_length,
+        )?;
+    }
+    Ok(result)
+}
+
+/// Starts the current executable again with elevated rights, prompting the user via UAC.
+///
+/// Returns after starting the new process, the current process is not terminated. If the user declines
+/// the UAC prompt, an error with [`ERROR_CANCELLED`](windows::Win32::Foundation::ERROR_CANCELLED)
+/// as the raw OS error code is returned.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winapi_easy::process::{
+///     is_elevated,
+///     relaunch_elevated,
+/// };
+///
+/// if !is_elevated()? {
+///     relaunch_elevated(&["--elevated"])?;
+///     std::process::exit(0);
+/// }
+///
+/// # Result::<(), std::io::Error>::Ok(())
+/// ```
+pub fn relaunch_elevated(args: &[&str]) -> io::Result<()> {
+    let executable = ZeroTerminatedWideString::from_os_str(std::env::current_exe()?);
+    let parameters = ZeroTerminatedWideString::from_os_str(
+        args.iter()
+            .map(|arg| quote_command_line_arg(arg))
+            .collect::<Vec<_>>()
+            .join(" "),
+    );
+    let directory = std::env::current_dir()?;
+    let directory = ZeroTerminatedWideString::from_os_str(directory);
+    let mut execute_info = SHELLEXECUTEINFOW {
+        cbSize: mem::size_of::<SHELLEXECUTEINFOW>().try_into().unwrap(),
+        fMask: SEE_MASK_NOASYNC,
+        lpVerb: w!("runas"),
+        lpFile: executable.as_raw_pcwstr(),
+        lpParameters: parameters.as_raw_pcwstr(),
+        lpDirectory: directory.as_raw_pcwstr(),
+        nShow: SW_SHOWNORMAL.0,
+        ..Default::default()
+    };
+    unsafe { ShellExecuteExW(&mut execute_info)? };
+    Ok(())
+}
+
+/// Quotes an argument so that it is parsed as a single argument by [`CommandLineToArgvW`]
+/// and the Microsoft C runtime.
+fn quote_command_line_arg(arg: &str) -> String {
+    if !arg.is_empty() && !arg.contains([' ', '\t', '\n', '\u{b}', '"']) {
+        return arg.to_string();
+    }
+    let mut result = String::from('"');
+    let mut backslash_count = 0;
+    for char in arg.chars() {
+        match char {
+            '\\' => backslash_count += 1,
+            '"' => {
+                // Backslashes before a quote need to be escaped, as well as the quote itself
+                result.push_str(&"\\".repeat(backslash_count * 2 + 1));
+                result.push('"');
+                backslash_count = 0;
+            }
+            _ => {
+                result.push_str(&"\\".repeat(backslash_count));
+                result.push(char);
+                backslash_count = 0;
+            }
+        }
+    }
+    // Trailing backslashes would escape the closing quote
+    result.push_str(&"\\".repeat(backslash_count * 2));
+    result.push('"');
+    result
 }
 
 #[cfg(test)]
@@ -469,6 +1911,183 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn set_affinity_mask() -> io::Result<()> {
+        let mut process = Process::current();
+        let process_mask = process.get_affinity_mask()?;
+        assert_ne!(process_mask, 0);
+        process.set_affinity_mask(process_mask)?;
+        let mut thread = Thread::current();
+        let previous_thread_mask = thread.set_affinity_mask(process_mask)?;
+        assert_ne!(previous_thread_mask, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn get_executable_path() -> io::Result<()> {
+        let path = ProcessId::current().get_executable_path()?;
+        assert_eq!(path, std::env::current_exe()?);
+        Ok(())
+    }
+
+    #[test]
+    fn get_integrity_level() -> io::Result<()> {
+        let level = ProcessId::current().get_integrity_level()?;
+        assert_eq!(level >= IntegrityLevel::High, is_elevated()?);
+        Ok(())
+    }
+
+    #[test]
+    fn get_command_line() -> io::Result<()> {
+        let command_line = Process::current().get_command_line()?;
+        assert_eq!(command_line.len(), std::env::args().count());
+        Ok(())
+    }
+
+    #[test]
+    fn create_suspended_remote_thread() -> io::Result<()> {
+        unsafe extern "system" fn thread_function(_parameter: *mut c_void) -> u32 {
+            0
+        }
+        let thread =
+            unsafe { Process::current().create_remote_thread(thread_function, None, true)? };
+        thread.resume()?;
+        Ok(())
+    }
+
+    #[test]
+    fn get_exported_symbols() -> io::Result<()> {
+        let module = ModuleHandle::get_loaded("kernel32.dll")?;
+        let symbols = module.get_exported_symbols()?;
+        assert!(symbols.iter().any(|symbol| symbol == "GetCurrentProcessId"));
+        Ok(())
+    }
+
+    #[test]
+    fn get_module_version_info() -> io::Result<()> {
+        let module = ModuleHandle::get_loaded("kernel32.dll")?;
+        assert!(module.get_path()?.is_absolute());
+        let version_info = module.get_version_info()?;
+        assert_gt!(version_info.file_version.major, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn acquire_single_instance() -> io::Result<()> {
+        const NAME: &str = "winapi-easy-test-single-instance";
+        let instance = SingleInstance::acquire(NAME)?;
+        assert!(instance.is_some());
+        assert!(SingleInstance::acquire(NAME)?.is_none());
+        drop(instance);
+        assert!(SingleInstance::acquire(NAME)?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn launch_process() -> io::Result<()> {
+        let command = CommandSpec::new("cmd.exe")
+            .args(["/c", "exit"])
+            .env("WINAPI_EASY_TEST", "1")
+            .start_suspended(true);
+        let launched = Process::launch_with_details(&command)?;
+        assert_ne!(launched.process.get_id(), ProcessId::current());
+        launched.main_thread.resume()?;
+        Ok(())
+    }
+
+    #[test]
+    fn launch_process_with_pipes() -> io::Result<()> {
+        let command = CommandSpec::new("cmd.exe")
+            .args(["/q", "/d", "/k", "prompt $s"])
+            .pipe_stdin(true)
+            .pipe_stdout(true)
+            .pipe_stderr(true);
+        let launched = Process::launch_with_details(&command)?;
+        let mut stdin = launched.stdin.unwrap();
+        stdin.write_all(b"echo piped_output\r\necho piped_error 1>&2\r\nexit\r\n")?;
+        drop(stdin);
+        let mut output = String::new();
+        launched.stdout.unwrap().read_to_string(&mut output)?;
+        let mut error_output = String::new();
+        launched.stderr.unwrap().read_to_string(&mut error_output)?;
+        assert!(output.contains("piped_output"));
+        assert!(error_output.contains("piped_error"));
+        Ok(())
+    }
+
+    #[test]
+    fn get_environment_block() {
+        let block = CommandSpec::new("test.exe")
+            .env_clear()
+            .env("b", "2")
+            .env("A", "1")
+            .env("c", "3")
+            .env_remove("C")
+            .get_environment_block()
+            .unwrap();
+        assert_eq!(block, "A=1\0b=2\0\0".encode_utf16().collect::<Vec<_>>());
+        let empty_block = CommandSpec::new("test.exe")
+            .env_clear()
+            .get_environment_block()
+            .unwrap();
+        assert_eq!(empty_block, [0, 0]);
+        assert!(CommandSpec::new("test.exe")
+            .get_environment_block()
+            .is_none());
+    }
+
+    #[test]
+    fn create_kill_on_close_job() -> io::Result<()> {
+        let job = JobObject::new()?;
+        job.set_kill_on_close()?;
+        assert_ne!(
+            job.get_extended_limit_info()?
+                .BasicLimitInformation
+                .LimitFlags
+                & JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+            Default::default()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn check_elevation() -> io::Result<()> {
+        if is_elevated()? {
+            assert!(!is_elevation_available()?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn quote_command_line_args() {
+        assert_eq!(quote_command_line_arg("simple"), "simple");
+        assert_eq!(quote_command_line_arg(""), r#""""#);
+        assert_eq!(quote_command_line_arg("with space"), r#""with space""#);
+        assert_eq!(quote_command_line_arg(r#"a"b"#), r#""a\"b""#);
+        assert_eq!(quote_command_line_arg(r"C:\my dir\"), r#""C:\my dir\\""#);
+        assert_eq!(quote_command_line_arg(r#"a\"b"#), r#""a\\\"b""#);
+    }
+
+    #[test]
+    fn get_env_var() -> io::Result<()> {
+        let path = super::get_env_var("Path", EnvScope::System)?;
+        assert!(path.is_some_and(|path| !path.is_empty()));
+        let missing = super::get_env_var("WINAPI_EASY_NON_EXISTING_VAR", EnvScope::User)?;
+        assert_eq!(missing, None);
+        Ok(())
+    }
+
+    #[test]
+    fn get_cpu_times() -> io::Result<()> {
+        let process_times = Process::current().get_cpu_times()?;
+        let thread_times = Thread::current().get_cpu_times()?;
+        assert!(process_times.exit_time.is_none());
+        assert!(thread_times.exit_time.is_none());
+        assert_le!(process_times.creation_time, thread_times.creation_time);
+        assert_lt!(thread_times.creation_time, SystemTime::now());
+        Ok(())
+    }
+
     #[test]
     fn set_get_io_priority() -> io::Result<()> {
         let mut curr_process = Process::current();
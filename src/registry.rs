@@ -0,0 +1,436 @@
+//! Windows registry.
+
+use std::io;
+use std::mem;
+
+use num_enum::IntoPrimitive;
+use windows::core::{
+    PCWSTR,
+    PWSTR,
+};
+use windows::Win32::Foundation::{
+    ERROR_FILE_NOT_FOUND,
+    ERROR_MORE_DATA,
+    ERROR_NO_MORE_ITEMS,
+    WIN32_ERROR,
+};
+use windows::Win32::System::Registry::{
+    RegCloseKey,
+    RegCreateKeyExW,
+    RegDeleteValueW,
+    RegEnumKeyExW,
+    RegEnumValueW,
+    RegOpenKeyExW,
+    RegQueryInfoKeyW,
+    RegQueryValueExW,
+    RegSetValueExW,
+    HKEY,
+    HKEY_CLASSES_ROOT,
+    HKEY_CURRENT_CONFIG,
+    HKEY_CURRENT_USER,
+    HKEY_LOCAL_MACHINE,
+    HKEY_USERS,
+    KEY_READ,
+    KEY_WRITE,
+    REG_BINARY,
+    REG_DWORD,
+    REG_EXPAND_SZ,
+    REG_MULTI_SZ,
+    REG_OPTION_NON_VOLATILE,
+    REG_QWORD,
+    REG_SAM_FLAGS,
+    REG_SZ,
+    REG_VALUE_TYPE,
+};
+
+use crate::string::{
+    FromWideString,
+    ToWideString,
+};
+
+/// A predefined top-level registry key.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum RegistryHive {
+    ClassesRoot,
+    CurrentUser,
+    LocalMachine,
+    Users,
+    CurrentConfig,
+}
+
+impl From<RegistryHive> for HKEY {
+    fn from(value: RegistryHive) -> Self {
+        match value {
+            RegistryHive::ClassesRoot => HKEY_CLASSES_ROOT,
+            RegistryHive::CurrentUser => HKEY_CURRENT_USER,
+            RegistryHive::LocalMachine => HKEY_LOCAL_MACHINE,
+            RegistryHive::Users => HKEY_USERS,
+            RegistryHive::CurrentConfig => HKEY_CURRENT_CONFIG,
+        }
+    }
+}
+
+/// Access rights requested when opening a [`RegistryKey`].
+#[derive(IntoPrimitive, Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(u32)]
+pub enum RegistryAccess {
+    Read = KEY_READ.0,
+    Write = KEY_WRITE.0,
+    ReadWrite = KEY_READ.0 | KEY_WRITE.0,
+}
+
+impl From<RegistryAccess> for REG_SAM_FLAGS {
+    fn from(value: RegistryAccess) -> Self {
+        REG_SAM_FLAGS(value.into())
+    }
+}
+
+/// Data stored in a registry value.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum RegistryValue {
+    String(String),
+    /// A string that may contain unexpanded references to environment variables, like `%USERPROFILE%`.
+    ExpandString(String),
+    MultiString(Vec<String>),
+    Dword(u32),
+    Qword(u64),
+    /// Binary data, also used for all other value types.
+    Binary(Vec<u8>),
+}
+
+impl RegistryValue {
+    fn from_raw(value_type: REG_VALUE_TYPE, data: Vec<u8>) -> Self {
+        fn to_wide_chars(data: &[u8]) -> Vec<u16> {
+            let mut wide_chars: Vec<u16> = data
+                .chunks_exact(mem::size_of::<u16>())
+                .map(|chunk| u16::from_le_bytes([chunk[0], chunk[1]]))
+                .collect();
+            // Strings are not guaranteed to be zero terminated
+            while wide_chars.last() == Some(&0) {
+                wide_chars.pop();
+            }
+            wide_chars
+        }
+        match value_type {
+            REG_SZ => Self::String(to_wide_chars(&data).to_string_lossy()),
+            REG_EXPAND_SZ => Self::ExpandString(to_wide_chars(&data).to_string_lossy()),
+            REG_MULTI_SZ => {
+                let wide_chars = to_wide_chars(&data);
+                let strings = if wide_chars.is_empty() {
+                    Vec::new()
+                } else {
+                    wide_chars
+                        .split(|x| *x == 0)
+                        .map(|x| x.to_string_lossy())
+                        .collect()
+                };
+                Self::MultiString(strings)
+            }
+            REG_DWORD if data.len() == mem::size_of::<u32>() => {
+                Self::Dword(u32::from_le_bytes(data.try_into().unwrap()))
+            }
+            REG_QWORD if data.len() == mem::size_of::<u64>() => {
+                Self::Qword(u64::from_le_bytes(data.try_into().unwrap()))
+            }
+            _ => Self::Binary(data),
+        }
+    }
+
+    fn to_raw(&self) -> (REG_VALUE_TYPE, Vec<u8>) {
+        fn to_bytes(wide_chars: impl IntoIterator<Item = u16>) -> Vec<u8> {
+            wide_chars.into_iter().flat_map(u16::to_le_bytes).collect()
+        }
+        match self {
+            Self::String(value) => (REG_SZ, to_bytes(value.to_wide_string())),
+            Self::ExpandString(value) => (REG_EXPAND_SZ, to_bytes(value.to_wide_string())),
+            Self::MultiString(values) => {
+                let wide_chars = values
+                    .iter()
+                    .flat_map(|value| value.to_wide_string())
+                    .chain([0]);
+                (REG_MULTI_SZ, to_bytes(wide_chars))
+            }
+            Self::Dword(value) => (REG_DWORD, value.to_le_bytes().to_vec()),
+            Self::Qword(value) => (REG_QWORD, value.to_le_bytes().to_vec()),
+            Self::Binary(value) => (REG_BINARY, value.clone()),
+        }
+    }
+}
+
+/// An open registry key, closed on drop.
+#[derive(Debug)]
+pub struct RegistryKey {
+    handle: HKEY,
+}
+
+impl RegistryKey {
+    /// Opens an existing key.
+    pub fn open(hive: RegistryHive, path: &str, access: RegistryAccess) -> io::Result<Self> {
+        let mut handle = HKEY::default();
+        unsafe {
+            RegOpenKeyExW(
+                HKEY::from(hive),
+                PCWSTR::from_raw(path.to_wide_string().as_ptr()),
+                0,
+                access.into(),
+                &mut handle,
+            )
+            .ok()?;
+        }
+        Ok(Self { handle })
+    }
+
+    /// Opens a key, creating it and all missing parent keys if necessary.
+    pub fn create(hive: RegistryHive, path: &str, access: RegistryAccess) -> io::Result<Self> {
+        let mut handle = HKEY::default();
+        unsafe {
+            RegCreateKeyExW(
+                HKEY::from(hive),
+                PCWSTR::from_raw(path.to_wide_string().as_ptr()),
+                0,
+                None,
+                REG_OPTION_NON_VOLATILE,
+                access.into(),
+                None,
+                &mut handle,
+                None,
+            )
+            .ok()?;
+        }
+        Ok(Self { handle })
+    }
+
+    /// Returns the value with the given name, or `None` if it doesn't exist.
+    ///
+    /// An empty name refers to the default value of the key.
+    pub fn get_value(&self, name: &str) -> io::Result<Option<RegistryValue>> {
+        let name = name.to_wide_string();
+        loop {
+            let mut value_type = REG_VALUE_TYPE::default();
+            let mut byte_size: u32 = 0;
+            let ret_val = unsafe {
+                RegQueryValueExW(
+                    self.handle,
+                    PCWSTR::from_raw(name.as_ptr()),
+                    None,
+                    None,
+                    None,
+                    Some(&mut byte_size),
+                )
+            };
+            match ret_val {
+                ERROR_FILE_NOT_FOUND => return Ok(None),
+                error => error.ok()?,
+            }
+            let mut data: Vec<u8> = vec![0; byte_size as usize];
+            let ret_val = unsafe {
+                RegQueryValueExW(
+                    self.handle,
+                    PCWSTR::from_raw(name.as_ptr()),
+                    None,
+                    Some(&mut value_type),
+                    Some(data.as_mut_ptr()),
+                    Some(&mut byte_size),
+                )
+            };
+            match ret_val {
+                // Value was changed in between and is now bigger
+                ERROR_MORE_DATA => continue,
+                ERROR_FILE_NOT_FOUND => return Ok(None),
+                error => error.ok()?,
+            }
+            data.truncate(byte_size as usize);
+            return Ok(Some(RegistryValue::from_raw(value_type, data)));
+        }
+    }
+
+    /// Creates or overwrites the value with the given name.
+    pub fn set_value(&self, name: &str, value: &RegistryValue) -> io::Result<()> {
+        let (value_type, data) = value.to_raw();
+        unsafe {
+            RegSetValueExW(
+                self.handle,
+                PCWSTR::from_raw(name.to_wide_string().as_ptr()),
+                0,
+                value_type,
+                Some(&data),
+            )
+            .ok()?;
+        }
+        Ok(())
+    }
+
+    /// Deletes the value with the given name.
+    pub fn delete_value(&self, name: &str) -> io::Result<()> {
+        unsafe {
+            RegDeleteValueW(
+                self.handle,
+                PCWSTR::from_raw(name.to_wide_string().as_ptr()),
+            )
+            .ok()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the names of all direct subkeys.
+    pub fn get_subkey_names(&self) -> io::Result<Vec<String>> {
+        let mut max_name_chars: u32 = 0;
+        unsafe {
+            RegQueryInfoKeyW(
+                self.handle,
+                PWSTR::null(),
+                None,
+                None,
+                None,
+                Some(&mut max_name_chars),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .ok()?;
+        }
+        self.enumerate_names(max_name_chars, |index, buffer, name_chars| unsafe {
+            RegEnumKeyExW(
+                self.handle,
+                index,
+                PWSTR::from_raw(buffer.as_mut_ptr()),
+                name_chars,
+                None,
+                PWSTR::null(),
+                None,
+                None,
+            )
+        })
+    }
+
+    /// Returns the names of all values of the key.
+    pub fn get_value_names(&self) -> io::Result<Vec<String>> {
+        let mut max_name_chars: u32 = 0;
+        unsafe {
+            RegQueryInfoKeyW(
+                self.handle,
+                PWSTR::null(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(&mut max_name_chars),
+                None,
+                None,
+                None,
+            )
+            .ok()?;
+        }
+        self.enumerate_names(max_name_chars, |index, buffer, name_chars| unsafe {
+            RegEnumValueW(
+                self.handle,
+                index,
+                PWSTR::from_raw(buffer.as_mut_ptr()),
+                name_chars,
+                None,
+                None,
+                None,
+                None,
+            )
+        })
+    }
+
+    fn enumerate_names<F>(&self, max_name_chars: u32, mut enum_fn: F) -> io::Result<Vec<String>>
+    where
+        F: FnMut(u32, &mut [u16], &mut u32) -> WIN32_ERROR,
+    {
+        // Includes space for the terminating zero
+        let mut buffer: Vec<u16> = vec![0; max_name_chars as usize + 1];
+        let mut result = Vec::new();
+        let mut index = 0;
+        loop {
+            let mut name_chars: u32 = buffer.len().try_into().unwrap();
+            match enum_fn(index, &mut buffer, &mut name_chars) {
+                ERROR_NO_MORE_ITEMS => break,
+                // A longer name was added in the meantime, retry with a bigger buffer
+                ERROR_MORE_DATA => {
+                    buffer.resize(buffer.len() * 2, 0);
+                    continue;
+                }
+                error => error.ok()?,
+            }
+            result.push((&buffer[..name_chars as usize]).to_string_lossy());
+            index += 1;
+        }
+        Ok(result)
+    }
+}
+
+impl Drop for RegistryKey {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = RegCloseKey(self.handle);
+        }
+    }
+}
+
+impl From<&RegistryKey> for HKEY {
+    /// Returns the underlying raw key handle used by [`windows`].
+    fn from(value: &RegistryKey) -> Self {
+        value.handle
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use more_asserts::*;
+
+    use super::*;
+
+    #[test]
+    fn enumerate_software_key() -> io::Result<()> {
+        let key = RegistryKey::open(RegistryHive::LocalMachine, "SOFTWARE", RegistryAccess::Read)?;
+        let subkey_names = key.get_subkey_names()?;
+        assert_gt!(subkey_names.len(), 0);
+        assert!(subkey_names.iter().any(|name| name == "Microsoft"));
+        std::hint::black_box(key.get_value_names()?);
+        Ok(())
+    }
+
+    #[test]
+    fn get_string_value() -> io::Result<()> {
+        let key = RegistryKey::open(
+            RegistryHive::LocalMachine,
+            r"SOFTWARE\Microsoft\Windows NT\CurrentVersion",
+            RegistryAccess::Read,
+        )?;
+        let value = key.get_value("ProductName")?;
+        assert!(matches!(value, Some(RegistryValue::String(_))));
+        assert_eq!(key.get_value("WinapiEasyNonExistingValue")?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn set_get_delete_value() -> io::Result<()> {
+        const VALUE_NAME: &str = "WinapiEasyTestValue";
+        let key = RegistryKey::open(
+            RegistryHive::CurrentUser,
+            "Software",
+            RegistryAccess::ReadWrite,
+        )?;
+        let values = [
+            RegistryValue::String("test".to_string()),
+            RegistryValue::MultiString(vec!["a".to_string(), "b".to_string()]),
+            RegistryValue::Dword(42),
+            RegistryValue::Qword(u64::MAX),
+            RegistryValue::Binary(vec![1, 2, 3]),
+        ];
+        for value in values {
+            key.set_value(VALUE_NAME, &value)?;
+            assert_eq!(key.get_value(VALUE_NAME)?, Some(value));
+        }
+        key.delete_value(VALUE_NAME)?;
+        assert_eq!(key.get_value(VALUE_NAME)?, None);
+        Ok(())
+    }
+}
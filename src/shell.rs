@@ -4,9 +4,18 @@ use num_enum::{
     FromPrimitive,
     IntoPrimitive,
 };
-use windows::Win32::UI::Shell::Common::ITEMIDLIST;
+use windows::Win32::UI::Shell::Common::{
+    COMDLG_FILTERSPEC,
+    ITEMIDLIST,
+};
 use windows::Win32::UI::Shell::{
+    FileOpenDialog,
+    FileSaveDialog,
+    IFileDialog,
+    IFileOpenDialog,
+    IFileSaveDialog,
     ILCreateFromPathW,
+    IShellItem,
     SHCNRF_InterruptLevel,
     SHCNRF_NewDelivery,
     SHCNRF_RecursiveInterrupt,
@@ -17,7 +26,15 @@ use windows::Win32::UI::Shell::{
     SHChangeNotifyDeregister,
     SHChangeNotifyEntry,
     SHChangeNotifyRegister,
+    SHCreateItemFromParsingName,
     SHGetPathFromIDListEx,
+    FILEOPENDIALOGOPTIONS,
+    FOS_ALLOWMULTISELECT,
+    FOS_FILEMUSTEXIST,
+    FOS_FORCEFILESYSTEM,
+    FOS_OVERWRITEPROMPT,
+    FOS_PICKFOLDERS,
+    HDROP,
     SHCNE_ASSOCCHANGED,
     SHCNE_CREATE,
     SHCNE_DELETE,
@@ -29,10 +46,15 @@ use windows::Win32::UI::Shell::{
     SHCNE_UPDATEDIR,
     SHCNE_UPDATEITEM,
     SHCNF_IDLIST,
+    SIGDN_FILESYSPATH,
 };
 use windows::Win32::UI::WindowsAndMessaging::WM_APP;
 
-use std::cell::Cell;
+use std::cell::{
+    Cell,
+    RefCell,
+};
+use std::marker::PhantomData;
 use std::ops::{
     BitOr,
     BitOrAssign,
@@ -43,27 +65,70 @@ use std::path::{
 };
 use std::{
     io,
+    mem,
     ptr,
+    slice,
+};
+use windows::core::{
+    implement,
+    GUID,
+    HRESULT,
+    PCWSTR,
 };
 use windows::Win32::Foundation::{
+    ERROR_CANCELLED,
     HANDLE,
     HWND,
     LPARAM,
+    POINTL,
+    S_OK,
     WPARAM,
 };
+use windows::Win32::System::Com::{
+    IDataObject,
+    DVASPECT_CONTENT,
+    FORMATETC,
+    TYMED_HGLOBAL,
+};
+use windows::Win32::System::Memory::GlobalSize;
+use windows::Win32::System::Ole::{
+    IDropTarget,
+    IDropTarget_Impl,
+    OleInitialize,
+    OleUninitialize,
+    RegisterDragDrop,
+    ReleaseStgMedium,
+    RevokeDragDrop,
+    CF_DIB,
+    CF_HDROP,
+    CF_UNICODETEXT,
+    CLIPBOARD_FORMAT,
+    DROPEFFECT,
+    DROPEFFECT_COPY,
+    DROPEFFECT_NONE,
+};
+use windows::Win32::System::SystemServices::MODIFIERKEYS_FLAGS;
 
-use crate::com::ComTaskMemory;
+use crate::com::{
+    ComInterfaceExt,
+    ComTaskMemory,
+};
 use crate::internal::{
+    catch_unwind_and_abort,
+    get_hdrop_file_list,
     CustomAutoDrop,
+    GlobalLockedData,
     ReturnValue,
 };
 use crate::messaging::ThreadMessageLoop;
 use crate::string::{
     max_path_extend,
+    FromWideString,
     ZeroTerminatedWideString,
 };
 use crate::ui::messaging::WindowMessageListener;
 use crate::ui::{
+    Point,
     Window,
     WindowClass,
     WindowClassAppearance,
@@ -226,8 +291,9 @@ where
     let window_class = WindowClass::register_new(
         "Shell Change Listener Class",
         WindowClassAppearance::empty(),
+        None,
     )?;
-    let window = Window::create_new(&window_class, &listener, "Shell Change Listener")?;
+    let window = Window::create_new_message_only(&window_class, &listener)?;
     let reg_id = unsafe {
         SHChangeNotifyRegister(
             HWND::from(window.as_ref()),
@@ -264,3 +330,343 @@ pub fn refresh_icon_cache() {
         SHChangeNotify(SHCNE_ASSOCCHANGED, SHCNF_IDLIST, None, None);
     }
 }
+
+/// Data dropped onto a window, see [`register_drop_target`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum DropPayload {
+    Files(Vec<PathBuf>),
+    Text(String),
+    /// A device independent bitmap (`CF_DIB`), consisting of a `BITMAPINFO` header followed by the bitmap bits.
+    Bitmap(Vec<u8>),
+}
+
+impl DropPayload {
+    /// Supported formats in order of preference.
+    const FORMATS: [CLIPBOARD_FORMAT; 3] = [CF_HDROP, CF_UNICODETEXT, CF_DIB];
+
+    fn from_data_object(data_object: &IDataObject) -> io::Result<Option<Self>> {
+        for format in Self::FORMATS {
+            let format_etc = Self::get_format_etc(format);
+            if unsafe { data_object.QueryGetData(&format_etc) } != S_OK {
+                continue;
+            }
+            let medium = CustomAutoDrop {
+                value: unsafe { data_object.GetData(&format_etc) }?,
+                drop_fn: |medium| unsafe { ReleaseStgMedium(medium) },
+            };
+            let mut locked_data = GlobalLockedData::lock(unsafe { medium.value.u.hGlobal })?;
+            let data_size = unsafe { GlobalSize(locked_data.handle()) };
+            let payload = match format {
+                CF_HDROP => Self::Files(get_hdrop_file_list(HDROP(locked_data.ptr()))?),
+                CF_UNICODETEXT => {
+                    let wide_chars = unsafe {
+                        slice::from_raw_parts(
+                            locked_data.ptr().cast::<u16>(),
+                            data_size / mem::size_of::<u16>(),
+                        )
+                    };
+                    let text_length = wide_chars
+                        .iter()
+                        .position(|x| *x == 0)
+                        .unwrap_or(wide_chars.len());
+                    Self::Text((&wide_chars[..text_length]).to_string_lossy())
+                }
+                _ => Self::Bitmap(
+                    unsafe { slice::from_raw_parts(locked_data.ptr().cast::<u8>(), data_size) }
+                        .to_vec(),
+                ),
+            };
+            return Ok(Some(payload));
+        }
+        Ok(None)
+    }
+
+    fn is_supported_by(data_object: &IDataObject) -> bool {
+        Self::FORMATS.into_iter().any(|format| unsafe {
+            data_object.QueryGetData(&Self::get_format_etc(format)) == S_OK
+        })
+    }
+
+    fn get_format_etc(format: CLIPBOARD_FORMAT) -> FORMATETC {
+        FORMATETC {
+            cfFormat: format.0,
+            ptd: ptr::null_mut(),
+            dwAspect: DVASPECT_CONTENT.0,
+            lindex: -1,
+            tymed: TYMED_HGLOBAL.0 as u32,
+        }
+    }
+}
+
+#[implement(IDropTarget)]
+struct DropTarget {
+    callback: RefCell<Box<dyn FnMut(DropPayload, Point)>>,
+    is_supported: Cell<bool>,
+}
+
+impl DropTarget_Impl {
+    fn get_drop_effect(&self) -> DROPEFFECT {
+        if self.is_supported.get() {
+            DROPEFFECT_COPY
+        } else {
+            DROPEFFECT_NONE
+        }
+    }
+}
+
+impl IDropTarget_Impl for DropTarget_Impl {
+    fn DragEnter(
+        &self,
+        data_object: Option<&IDataObject>,
+        _key_state: MODIFIERKEYS_FLAGS,
+        _point: &POINTL,
+        effect: *mut DROPEFFECT,
+    ) -> windows::core::Result<()> {
+        self.is_supported
+            .set(data_object.is_some_and(DropPayload::is_supported_by));
+        unsafe { *effect = self.get_drop_effect() };
+        Ok(())
+    }
+
+    fn DragOver(
+        &self,
+        _key_state: MODIFIERKEYS_FLAGS,
+        _point: &POINTL,
+        effect: *mut DROPEFFECT,
+    ) -> windows::core::Result<()> {
+        unsafe { *effect = self.get_drop_effect() };
+        Ok(())
+    }
+
+    fn DragLeave(&self) -> windows::core::Result<()> {
+        self.is_supported.set(false);
+        Ok(())
+    }
+
+    fn Drop(
+        &self,
+        data_object: Option<&IDataObject>,
+        _key_state: MODIFIERKEYS_FLAGS,
+        point: &POINTL,
+        effect: *mut DROPEFFECT,
+    ) -> windows::core::Result<()> {
+        let payload = data_object
+            .map(DropPayload::from_data_object)
+            .transpose()?
+            .flatten();
+        unsafe { *effect = DROPEFFECT_NONE };
+        if let Some(payload) = payload {
+            let call = || {
+                let mut callback = self.callback.borrow_mut();
+                callback(
+                    payload,
+                    Point {
+                        x: point.x,
+                        y: point.y,
+                    },
+                );
+            };
+            catch_unwind_and_abort(call);
+            unsafe { *effect = DROPEFFECT_COPY };
+        }
+        self.is_supported.set(false);
+        Ok(())
+    }
+}
+
+/// A window registered as a drop target, see [`register_drop_target`].
+///
+/// The registration is revoked on drop, releasing the OLE initialization it holds.
+#[derive(Debug)]
+pub struct DropTargetRegistration<'a> {
+    window: HWND,
+    phantom: PhantomData<&'a WindowHandle>,
+}
+
+impl Drop for DropTargetRegistration<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = RevokeDragDrop(self.window);
+            OleUninitialize();
+        }
+    }
+}
+
+/// Registers a window as a target for OLE drag & drop.
+///
+/// In contrast to [`WindowHandle::set_accept_files`], this also supports dropping text and bitmaps
+/// from other applications. The callback receives the dropped data and the drop point in screen coordinates.
+///
+/// Must be called from the thread that created the window. Initializes OLE for the thread
+/// for as long as the returned registration is alive.
+pub fn register_drop_target<F>(
+    window: &WindowHandle,
+    callback: F,
+) -> io::Result<DropTargetRegistration<'_>>
+where
+    F: FnMut(DropPayload, Point) + 'static,
+{
+    unsafe { OleInitialize(None)? };
+    let drop_target: IDropTarget = DropTarget {
+        callback: RefCell::new(Box::new(callback)),
+        is_supported: Cell::new(false),
+    }
+    .into();
+    if let Err(err) = unsafe { RegisterDragDrop(HWND::from(window), &drop_target) } {
+        unsafe { OleUninitialize() };
+        return Err(err.into());
+    }
+    Ok(DropTargetRegistration {
+        window: window.into(),
+        phantom: PhantomData,
+    })
+}
+
+/// Options for [`open_file_dialog`], [`open_files_dialog`], [`save_file_dialog`] and [`pick_folder`].
+#[derive(Copy, Clone, Default, Debug)]
+pub struct FileDialogOptions<'a> {
+    /// Window to make the dialog modal to.
+    pub owner: Option<&'a WindowHandle>,
+    pub title: Option<&'a str>,
+    /// File type filters as pairs of display name and semicolon-separated patterns,
+    /// e.g. `("Text files", "*.txt;*.md")`.
+    ///
+    /// Ignored by [`pick_folder`].
+    pub filters: &'a [(&'a str, &'a str)],
+    pub initial_directory: Option<&'a Path>,
+    /// Initially entered file name.
+    pub file_name: Option<&'a str>,
+}
+
+impl ComInterfaceExt for IFileOpenDialog {
+    const CLASS_GUID: GUID = FileOpenDialog;
+}
+
+impl ComInterfaceExt for IFileSaveDialog {
+    const CLASS_GUID: GUID = FileSaveDialog;
+}
+
+/// Shows a dialog for selecting an existing file.
+///
+/// Returns `None` if the dialog is canceled.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winapi_easy::shell::{
+///     open_file_dialog,
+///     FileDialogOptions,
+/// };
+///
+/// let path = open_file_dialog(FileDialogOptions {
+///     filters: &[("Text files", "*.txt")],
+///     ..Default::default()
+/// })?;
+///
+/// # Result::<(), std::io::Error>::Ok(())
+/// ```
+pub fn open_file_dialog(options: FileDialogOptions) -> io::Result<Option<PathBuf>> {
+    let dialog = IFileOpenDialog::new_instance()?;
+    if !show_file_dialog(&dialog, options, FOS_FILEMUSTEXIST)? {
+        return Ok(None);
+    }
+    Ok(Some(get_shell_item_path(&unsafe { dialog.GetResult() }?)?))
+}
+
+/// Shows a dialog for selecting one or more existing files.
+///
+/// Returns `None` if the dialog is canceled.
+pub fn open_files_dialog(options: FileDialogOptions) -> io::Result<Option<Vec<PathBuf>>> {
+    let dialog = IFileOpenDialog::new_instance()?;
+    if !show_file_dialog(&dialog, options, FOS_FILEMUSTEXIST | FOS_ALLOWMULTISELECT)? {
+        return Ok(None);
+    }
+    let items = unsafe { dialog.GetResults() }?;
+    let paths = (0..unsafe { items.GetCount() }?)
+        .map(|index| get_shell_item_path(&unsafe { items.GetItemAt(index) }?))
+        .collect::<io::Result<_>>()?;
+    Ok(Some(paths))
+}
+
+/// Shows a dialog for selecting a path to save a file to.
+///
+/// The user will be asked for confirmation when selecting an existing file.
+/// Returns `None` if the dialog is canceled.
+pub fn save_file_dialog(options: FileDialogOptions) -> io::Result<Option<PathBuf>> {
+    let dialog = IFileSaveDialog::new_instance()?;
+    if !show_file_dialog(&dialog, options, FOS_OVERWRITEPROMPT)? {
+        return Ok(None);
+    }
+    Ok(Some(get_shell_item_path(&unsafe { dialog.GetResult() }?)?))
+}
+
+/// Shows a dialog for selecting an existing folder.
+///
+/// Returns `None` if the dialog is canceled.
+pub fn pick_folder(options: FileDialogOptions) -> io::Result<Option<PathBuf>> {
+    let dialog = IFileOpenDialog::new_instance()?;
+    let options = FileDialogOptions {
+        filters: &[],
+        ..options
+    };
+    if !show_file_dialog(&dialog, options, FOS_PICKFOLDERS)? {
+        return Ok(None);
+    }
+    Ok(Some(get_shell_item_path(&unsafe { dialog.GetResult() }?)?))
+}
+
+/// Shows the dialog, returning `false` if canceled.
+fn show_file_dialog(
+    dialog: &IFileDialog,
+    options: FileDialogOptions,
+    additional_flags: FILEOPENDIALOGOPTIONS,
+) -> io::Result<bool> {
+    unsafe {
+        dialog.SetOptions(dialog.GetOptions()? | FOS_FORCEFILESYSTEM | additional_flags)?;
+        if let Some(title) = options.title {
+            dialog.SetTitle(ZeroTerminatedWideString::from_os_str(title).as_raw_pcwstr())?;
+        }
+        if !options.filters.is_empty() {
+            let wide_filters: Vec<(ZeroTerminatedWideString, ZeroTerminatedWideString)> = options
+                .filters
+                .iter()
+                .map(|(name, pattern)| {
+                    (
+                        ZeroTerminatedWideString::from_os_str(name),
+                        ZeroTerminatedWideString::from_os_str(pattern),
+                    )
+                })
+                .collect();
+            let filter_specs: Vec<COMDLG_FILTERSPEC> = wide_filters
+                .iter()
+                .map(|(name, pattern)| COMDLG_FILTERSPEC {
+                    pszName: name.as_raw_pcwstr(),
+                    pszSpec: pattern.as_raw_pcwstr(),
+                })
+                .collect();
+            dialog.SetFileTypes(&filter_specs)?;
+        }
+        if let Some(initial_directory) = options.initial_directory {
+            let folder: IShellItem = SHCreateItemFromParsingName(
+                ZeroTerminatedWideString::from_os_str(initial_directory).as_raw_pcwstr(),
+                None,
+            )?;
+            dialog.SetFolder(&folder)?;
+        }
+        if let Some(file_name) = options.file_name {
+            dialog.SetFileName(ZeroTerminatedWideString::from_os_str(file_name).as_raw_pcwstr())?;
+        }
+        let owner = options.owner.map(HWND::from).unwrap_or_default();
+        match dialog.Show(owner) {
+            Ok(()) => Ok(true),
+            Err(err) if err.code() == HRESULT::from_win32(ERROR_CANCELLED.0) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+fn get_shell_item_path(item: &IShellItem) -> io::Result<PathBuf> {
+    let raw_path = ComTaskMemory(unsafe { item.GetDisplayName(SIGDN_FILESYSPATH) }?.0);
+    let path = unsafe { PCWSTR::from_raw(raw_path.0).as_wide() }.to_os_string();
+    Ok(path.into())
+}
@@ -1,19 +1,37 @@
 //! UI components: Windows, taskbar.
 
+use std::cell::{
+    Cell,
+    RefCell,
+};
+use std::collections::HashMap;
 use std::error::Error;
+use std::ffi::c_void;
 use std::fmt::{
     Display,
     Formatter,
 };
 use std::marker::PhantomData;
 use std::mem;
+use std::ops::{
+    BitOr,
+    BitOrAssign,
+};
 use std::ptr::NonNull;
+use std::rc::Rc;
+use std::time::{
+    Duration,
+    Instant,
+};
 use std::{
     io,
+    ptr,
+    thread,
     vec,
 };
 
 use num_enum::{
+    FromPrimitive,
     IntoPrimitive,
     TryFromPrimitive,
 };
@@ -25,6 +43,10 @@ use windows::Win32::Foundation::{
     GetLastError,
     SetLastError,
     BOOL,
+    COLORREF,
+    ERROR_TIMEOUT,
+    HANDLE,
+    HMODULE,
     HWND,
     LPARAM,
     NO_ERROR,
@@ -32,15 +54,56 @@ use windows::Win32::Foundation::{
     RECT,
     WPARAM,
 };
+use windows::Win32::Graphics::Dwm::{
+    DwmGetColorizationColor,
+    DwmGetWindowAttribute,
+    DWMWA_CLOAKED,
+    DWMWA_EXTENDED_FRAME_BOUNDS,
+};
+use windows::Win32::Graphics::Gdi::{
+    ClientToScreen,
+    InvalidateRect,
+    RedrawWindow,
+    SetWindowRgn,
+    HRGN,
+    RDW_FRAME,
+    RDW_INVALIDATE,
+    RDW_UPDATENOW,
+};
 use windows::Win32::System::Console::{
     AllocConsole,
     FreeConsole,
     GetConsoleWindow,
 };
+use windows::Win32::System::RemoteDesktop::{
+    WTSRegisterSessionNotification,
+    WTSUnRegisterSessionNotification,
+    NOTIFY_FOR_THIS_SESSION,
+};
 use windows::Win32::System::Shutdown::LockWorkStation;
+use windows::Win32::UI::Accessibility::{
+    SetWinEventHook,
+    UnhookWinEvent,
+    HWINEVENTHOOK,
+};
+use windows::Win32::UI::HiDpi::{
+    AreDpiAwarenessContextsEqual,
+    GetDpiForWindow,
+    GetThreadDpiAwarenessContext,
+    SetProcessDpiAwarenessContext,
+    DPI_AWARENESS_CONTEXT,
+    DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE,
+    DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2,
+    DPI_AWARENESS_CONTEXT_SYSTEM_AWARE,
+    DPI_AWARENESS_CONTEXT_UNAWARE,
+    DPI_AWARENESS_CONTEXT_UNAWARE_GDISCALED,
+};
 use windows::Win32::UI::Input::KeyboardAndMouse::SetActiveWindow;
 use windows::Win32::UI::Shell::{
+    DragAcceptFiles,
     ITaskbarList3,
+    IVirtualDesktopManager,
+    Shell_NotifyIconGetRect,
     Shell_NotifyIconW,
     TaskbarList,
     NIF_GUID,
@@ -53,9 +116,12 @@ use windows::Win32::UI::Shell::{
     NIM_ADD,
     NIM_DELETE,
     NIM_MODIFY,
+    NIM_SETFOCUS,
     NIM_SETVERSION,
     NIS_HIDDEN,
     NOTIFYICONDATAW,
+    NOTIFYICONIDENTIFIER,
+    NOTIFYICON_VERSION,
     NOTIFYICON_VERSION_4,
     NOTIFY_ICON_INFOTIP_FLAGS,
     NOTIFY_ICON_STATE,
@@ -73,28 +139,72 @@ use windows::Win32::UI::Shell::{
     TBPF_PAUSED,
 };
 use windows::Win32::UI::WindowsAndMessaging::{
+    AnimateWindow,
+    ClipCursor,
     CreateWindowExW,
     DestroyWindow,
     EnumWindows,
+    FindWindowExW,
     FlashWindowEx,
+    GetAncestor,
+    GetClassLongPtrW,
+    GetClassLongW,
     GetClassNameW,
+    GetClientRect,
     GetDesktopWindow,
     GetForegroundWindow,
+    GetLastActivePopup,
+    GetPropW,
+    GetTopWindow,
+    GetWindow,
+    GetWindowInfo,
     GetWindowLongPtrW,
     GetWindowPlacement,
+    GetWindowRect,
     GetWindowTextLengthW,
     GetWindowTextW,
+    IsIconic,
     IsWindow,
     IsWindowVisible,
+    IsZoomed,
+    PostMessageW,
     RegisterClassExW,
+    RegisterDeviceNotificationW,
+    RemovePropW,
+    SendMessageTimeoutW,
     SendMessageW,
     SetForegroundWindow,
+    SetLayeredWindowAttributes,
+    SetParent,
+    SetPropW,
     SetWindowLongPtrW,
     SetWindowPlacement,
+    SetWindowPos,
     SetWindowTextW,
     ShowWindow,
     UnregisterClassW,
+    UnregisterDeviceNotification,
+    ANIMATE_WINDOW_FLAGS,
+    AW_BLEND,
+    AW_CENTER,
+    AW_HIDE,
+    AW_HOR_NEGATIVE,
+    AW_HOR_POSITIVE,
+    AW_SLIDE,
+    AW_VER_NEGATIVE,
+    AW_VER_POSITIVE,
+    CS_DBLCLKS,
+    CS_DROPSHADOW,
+    CS_HREDRAW,
+    CS_NOCLOSE,
+    CS_OWNDC,
+    CS_VREDRAW,
     CW_USEDEFAULT,
+    DBT_DEVTYP_DEVICEINTERFACE,
+    DEVICE_NOTIFY_ALL_INTERFACE_CLASSES,
+    DEVICE_NOTIFY_WINDOW_HANDLE,
+    DEV_BROADCAST_DEVICEINTERFACE_W,
+    EVENT_SYSTEM_FOREGROUND,
     FLASHWINFO,
     FLASHWINFO_FLAGS,
     FLASHW_ALL,
@@ -103,14 +213,40 @@ use windows::Win32::UI::WindowsAndMessaging::{
     FLASHW_TIMER,
     FLASHW_TIMERNOFG,
     FLASHW_TRAY,
+    GA_ROOTOWNER,
+    GCLP_HICON,
+    GCW_ATOM,
     GWLP_USERDATA,
+    GWL_EXSTYLE,
+    GWL_STYLE,
+    GW_HWNDNEXT,
+    GW_HWNDPREV,
+    HDEVNOTIFY,
     HICON,
+    HWND_MESSAGE,
+    HWND_TOPMOST,
+    ICON_BIG,
+    ICON_SMALL2,
+    LWA_ALPHA,
+    OBJID_WINDOW,
     SC_CLOSE,
     SC_MAXIMIZE,
     SC_MINIMIZE,
     SC_MONITORPOWER,
     SC_RESTORE,
+    SEND_MESSAGE_TIMEOUT_FLAGS,
     SHOW_WINDOW_CMD,
+    SMTO_ABORTIFHUNG,
+    SMTO_BLOCK,
+    SMTO_ERRORONEXIT,
+    SMTO_NORMAL,
+    SMTO_NOTIMEOUTIFNOTHUNG,
+    SWP_FRAMECHANGED,
+    SWP_NOACTIVATE,
+    SWP_NOMOVE,
+    SWP_NOSIZE,
+    SWP_NOZORDER,
+    SWP_SHOWWINDOW,
     SW_HIDE,
     SW_MAXIMIZE,
     SW_MINIMIZE,
@@ -121,33 +257,87 @@ use windows::Win32::UI::WindowsAndMessaging::{
     SW_SHOWNA,
     SW_SHOWNOACTIVATE,
     SW_SHOWNORMAL,
+    WINDOWINFO,
     WINDOWPLACEMENT,
+    WINDOWPLACEMENT_FLAGS,
+    WINDOW_EX_STYLE,
+    WINDOW_LONG_PTR_INDEX,
+    WINDOW_STYLE,
+    WINEVENT_OUTOFCONTEXT,
+    WM_CHAR,
+    WM_GETICON,
+    WM_GETTEXT,
+    WM_GETTEXTLENGTH,
+    WM_SETTEXT,
     WM_SYSCOMMAND,
     WNDCLASSEXW,
+    WNDCLASS_STYLES,
     WPF_SETMINPOSITION,
+    WS_ACTIVECAPTION,
+    WS_BORDER,
+    WS_CAPTION,
+    WS_CHILD,
+    WS_CLIPCHILDREN,
+    WS_CLIPSIBLINGS,
+    WS_DISABLED,
+    WS_EX_ACCEPTFILES,
+    WS_EX_APPWINDOW,
+    WS_EX_CLIENTEDGE,
+    WS_EX_LAYERED,
+    WS_EX_NOACTIVATE,
+    WS_EX_TOOLWINDOW,
+    WS_EX_TOPMOST,
+    WS_EX_TRANSPARENT,
+    WS_MAXIMIZE,
+    WS_MAXIMIZEBOX,
+    WS_MINIMIZE,
+    WS_MINIMIZEBOX,
     WS_OVERLAPPEDWINDOW,
+    WS_POPUP,
+    WS_SYSMENU,
+    WS_THICKFRAME,
+    WS_VISIBLE,
 };
 
 use crate::com::ComInterfaceExt;
 use crate::internal::{
+    catch_unwind_and_abort,
     custom_err_with_code,
     with_sync_closure_to_callback2,
     ReturnValue,
 };
+use crate::messaging::ThreadMessageLoop;
 #[cfg(feature = "process")]
 use crate::process::{
+    IntegrityLevel,
     ProcessId,
     ThreadId,
 };
+use crate::registry::{
+    RegistryAccess,
+    RegistryHive,
+    RegistryKey,
+    RegistryValue,
+};
 use crate::string::{
     to_wide_chars_iter,
     FromWideString,
     ToWideString,
+    ZeroTerminatedWideString,
+};
+use crate::ui::menu::{
+    MenuItem,
+    PopupMenu,
 };
 use crate::ui::messaging::{
     generic_window_proc,
     WindowMessageListener,
 };
+use crate::ui::paint::{
+    Region,
+    Rgb,
+    WindowDc,
+};
 use crate::ui::resource::{
     Brush,
     BuiltinColor,
@@ -155,11 +345,14 @@ use crate::ui::resource::{
     BuiltinIcon,
     Cursor,
     Icon,
+    OwnedIcon,
 };
 
+pub mod desktop;
 pub mod menu;
 pub mod message_box;
 pub mod messaging;
+pub mod paint;
 pub mod resource;
 
 /// A (non-null) handle to a window.
@@ -218,6 +411,124 @@ impl WindowHandle {
         Ok(result)
     }
 
+    /// Returns the top-level window at the top of the Z-order.
+    ///
+    /// Together with [`Self::get_next_in_zorder`], this allows walking all top-level windows from top to bottom.
+    pub fn get_top_window() -> Option<Self> {
+        let handle = unsafe { GetTopWindow(None) };
+        handle.ok().and_then(Self::from_maybe_null)
+    }
+
+    /// Returns the child window at the top of the Z-order of this window's children.
+    pub fn get_top_child(&self) -> Option<Self> {
+        let handle = unsafe { GetTopWindow(self.raw_handle) };
+        handle.ok().and_then(Self::from_maybe_null)
+    }
+
+    /// Returns the window below this one in the Z-order.
+    ///
+    /// The returned window is of the same kind as this one, i.e. a topmost, top-level or child window.
+    /// Returns `None` if this is the bottom window.
+    pub fn get_next_in_zorder(&self) -> Option<Self> {
+        let handle = unsafe { GetWindow(self.raw_handle, GW_HWNDNEXT) };
+        handle.ok().and_then(Self::from_maybe_null)
+    }
+
+    /// Returns the window above this one in the Z-order.
+    ///
+    /// The returned window is of the same kind as this one, i.e. a topmost, top-level or child window.
+    /// Returns `None` if this is the top window.
+    pub fn get_prev_in_zorder(&self) -> Option<Self> {
+        let handle = unsafe { GetWindow(self.raw_handle, GW_HWNDPREV) };
+        handle.ok().and_then(Self::from_maybe_null)
+    }
+
+    /// Waits until a top-level window matching the given criteria exists and returns it.
+    ///
+    /// Returns `None` if the timeout expired first. Waits indefinitely if no timeout is given.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use winapi_easy::ui::{
+    ///     WindowHandle,
+    ///     WindowMatcher,
+    /// };
+    ///
+    /// let matcher = WindowMatcher {
+    ///     class_name: Some("Notepad"),
+    ///     ..Default::default()
+    /// };
+    /// let maybe_window = WindowHandle::wait_for(matcher, Some(Duration::from_secs(10)))?;
+    ///
+    /// # Result::<(), std::io::Error>::Ok(())
+    /// ```
+    pub fn wait_for(matcher: WindowMatcher, timeout: Option<Duration>) -> io::Result<Option<Self>> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(100);
+        let start = Instant::now();
+        loop {
+            let maybe_window = Self::get_toplevel_windows()?
+                .into_iter()
+                .find(|window| matcher.matches(window));
+            if maybe_window.is_some() {
+                return Ok(maybe_window);
+            }
+            let sleep_duration = match timeout {
+                Some(timeout) => match timeout.checked_sub(start.elapsed()) {
+                    Some(remaining) if !remaining.is_zero() => remaining.min(POLL_INTERVAL),
+                    _ => return Ok(None),
+                },
+                None => POLL_INTERVAL,
+            };
+            thread::sleep(sleep_duration);
+        }
+    }
+
+    /// Finds a top-level window by its class name and / or caption, both compared case-insensitively.
+    ///
+    /// A criterion set to `None` matches any window.
+    pub fn find(class_name: Option<&str>, window_name: Option<&str>) -> Option<Self> {
+        Self::find_internal(None, None, class_name, window_name)
+    }
+
+    /// Finds a direct child window of this window by its class name and / or caption,
+    /// both compared case-insensitively.
+    ///
+    /// The search begins after the child window `after`, or with the first child window if not given.
+    pub fn find_child(
+        &self,
+        after: Option<&WindowHandle>,
+        class_name: Option<&str>,
+        window_name: Option<&str>,
+    ) -> Option<Self> {
+        Self::find_internal(Some(self), after, class_name, window_name)
+    }
+
+    fn find_internal(
+        parent: Option<&WindowHandle>,
+        after: Option<&WindowHandle>,
+        class_name: Option<&str>,
+        window_name: Option<&str>,
+    ) -> Option<Self> {
+        let class_name_wide = class_name.map(|class_name| class_name.to_wide_string());
+        let window_name_wide = window_name.map(|window_name| window_name.to_wide_string());
+        let to_pcwstr = |wide: &Option<Vec<u16>>| {
+            wide.as_ref()
+                .map_or(PCWSTR::null(), |wide| PCWSTR::from_raw(wide.as_ptr()))
+        };
+        let handle = unsafe {
+            FindWindowExW(
+                parent.map(|parent| parent.raw_handle).unwrap_or_default(),
+                after.map(|after| after.raw_handle).unwrap_or_default(),
+                to_pcwstr(&class_name_wide),
+                to_pcwstr(&window_name_wide),
+            )
+        };
+        handle.ok().and_then(Self::from_maybe_null)
+    }
+
     pub(crate) fn from_non_null(handle: HWND) -> Self {
         Self {
             raw_handle: handle,
@@ -247,6 +558,49 @@ impl WindowHandle {
         result.as_bool()
     }
 
+    /// Returns `true` if the window is cloaked, meaning it is not shown even though it is technically visible.
+    ///
+    /// This applies for example to windows on other virtual desktops and suspended UWP apps.
+    pub fn is_cloaked(&self) -> io::Result<bool> {
+        let mut cloaked_reason: u32 = 0;
+        unsafe {
+            DwmGetWindowAttribute(
+                self.raw_handle,
+                DWMWA_CLOAKED,
+                &mut cloaked_reason as *mut u32 as *mut c_void,
+                mem::size_of::<u32>().try_into().unwrap(),
+            )?;
+        }
+        Ok(cloaked_reason != 0)
+    }
+
+    /// Returns `true` if the window would be shown in the Alt+Tab window switcher.
+    ///
+    /// Useful to filter the results of [`Self::get_toplevel_windows`] for windows the user would consider open.
+    pub fn is_alt_tab_window(&self) -> io::Result<bool> {
+        if !self.is_visible() || self.is_cloaked()? {
+            return Ok(false);
+        }
+        let raw_extended_style = u32::from(self.get_extended_style());
+        if raw_extended_style & u32::from(WindowExtendedStyle::ToolWindow) != 0 {
+            return Ok(false);
+        }
+        // The window must be the last visible active popup of its root owner.
+        // See also: https://devblogs.microsoft.com/oldnewthing/20071008-00/?p=24863
+        let mut walk = unsafe { GetAncestor(self.raw_handle, GA_ROOTOWNER) };
+        loop {
+            let last_popup = unsafe { GetLastActivePopup(walk) };
+            if last_popup == walk {
+                break;
+            }
+            walk = last_popup;
+            if unsafe { IsWindowVisible(walk) }.as_bool() {
+                break;
+            }
+        }
+        Ok(walk == self.raw_handle)
+    }
+
     /// Returns the window caption text, converted to UTF-8 in a potentially lossy way.
     pub fn get_caption_text(&self) -> String {
         let required_length = unsafe { GetWindowTextLengthW(self.raw_handle) };
@@ -266,6 +620,40 @@ impl WindowHandle {
         buffer.to_string_lossy()
     }
 
+    /// Returns the window text by sending [`WM_GETTEXT`], converted to UTF-8 in a potentially lossy way.
+    ///
+    /// Unlike [`Self::get_caption_text`], this also retrieves the content of controls belonging to other
+    /// processes, e.g. edit controls. Will block if the owning thread of the window is not responding.
+    pub fn get_text_via_message(&self) -> io::Result<String> {
+        let required_length = unsafe {
+            SendMessageW(
+                self.raw_handle,
+                WM_GETTEXTLENGTH,
+                WPARAM::default(),
+                LPARAM::default(),
+            )
+        };
+        let required_length = match usize::try_from(required_length.0) {
+            Ok(0) | Err(_) => return Ok(String::new()),
+            Ok(length) => length + 1,
+        };
+
+        let mut buffer: Vec<u16> = vec![0; required_length];
+        let copied_chars = unsafe {
+            SendMessageW(
+                self.raw_handle,
+                WM_GETTEXT,
+                WPARAM(required_length),
+                LPARAM(buffer.as_mut_ptr() as isize),
+            )
+        };
+        let copied_chars = usize::try_from(copied_chars.0)
+            .map_err(|_| custom_err_with_code("Cannot get window text", copied_chars.0))?;
+        // The text length can change between the 2 messages
+        buffer.truncate(copied_chars.min(required_length - 1));
+        Ok(buffer.to_string_lossy())
+    }
+
     /// Sets the window caption text.
     pub fn set_caption_text(&self, text: &str) -> io::Result<()> {
         let ret_val = unsafe {
@@ -278,7 +666,50 @@ impl WindowHandle {
         Ok(())
     }
 
+    /// Sets the window text by sending [`WM_SETTEXT`].
+    ///
+    /// Unlike [`Self::set_caption_text`], this also sets the content of controls belonging to other
+    /// processes, e.g. edit controls. Will block if the owning thread of the window is not responding.
+    pub fn set_text_via_message(&self, text: &str) -> io::Result<()> {
+        let wide_text = ZeroTerminatedWideString::from_os_str(text);
+        let result = unsafe {
+            SendMessageW(
+                self.raw_handle,
+                WM_SETTEXT,
+                WPARAM::default(),
+                LPARAM(wide_text.as_raw_pcwstr().as_ptr() as isize),
+            )
+        };
+        // Some controls return negative error values instead of `FALSE`
+        if result.0 <= 0 {
+            return Err(custom_err_with_code("Cannot set window text", result.0));
+        }
+        Ok(())
+    }
+
+    /// Types the text into the window by posting a [`WM_CHAR`] message for each character.
+    ///
+    /// Other than sending global keyboard input, this works without the window having the keyboard focus.
+    /// The messages are processed asynchronously by the window.
+    pub fn type_text(&self, text: &str) -> io::Result<()> {
+        for wide_char in text.encode_utf16() {
+            unsafe {
+                PostMessageW(
+                    self.raw_handle,
+                    WM_CHAR,
+                    WPARAM(wide_char.into()),
+                    LPARAM::default(),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
     /// Brings the window to the foreground.
+    ///
+    /// Windows only allows this under certain conditions, e.g. if the calling process is the foreground process
+    /// or if no user input happened within the foreground lock timeout, see
+    /// [`desktop::set_foreground_lock_timeout`].
     pub fn set_as_foreground(&self) -> io::Result<()> {
         unsafe {
             SetForegroundWindow(self.raw_handle).if_null_to_error_else_drop(|| {
@@ -314,6 +745,216 @@ impl WindowHandle {
         }
     }
 
+    /// Shows the window using an animation, blocking until it is finished.
+    ///
+    /// The window is not activated. Animations do not work well with layered windows,
+    /// e.g. after using [`Self::set_opacity`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use winapi_easy::ui::{
+    ///     AnimateEffect,
+    ///     AnimateDirection,
+    ///     WindowHandle,
+    /// };
+    ///
+    /// let window = WindowHandle::find(Some("MyPopupClass"), None).unwrap();
+    /// window.animate_show(
+    ///     AnimateEffect::Slide(AnimateDirection::BottomToTop),
+    ///     Duration::from_millis(200),
+    /// )?;
+    ///
+    /// # Result::<(), std::io::Error>::Ok(())
+    /// ```
+    pub fn animate_show(&self, effect: AnimateEffect, duration: Duration) -> io::Result<()> {
+        self.animate(effect.into(), duration)
+    }
+
+    /// Hides the window using an animation, blocking until it is finished.
+    ///
+    /// See also [`Self::animate_show`].
+    pub fn animate_hide(&self, effect: AnimateEffect, duration: Duration) -> io::Result<()> {
+        self.animate(ANIMATE_WINDOW_FLAGS::from(effect) | AW_HIDE, duration)
+    }
+
+    fn animate(&self, flags: ANIMATE_WINDOW_FLAGS, duration: Duration) -> io::Result<()> {
+        let millis = duration.as_millis().try_into().unwrap_or(u32::MAX);
+        unsafe { AnimateWindow(self.raw_handle, millis, flags)? };
+        Ok(())
+    }
+
+    /// Marks the whole client area as invalid, causing the window to be repainted.
+    ///
+    /// The window's listener will then receive a paint message, see
+    /// [`messaging::WindowMessageListener::handle_paint`].
+    pub fn redraw(&self) -> io::Result<()> {
+        unsafe {
+            InvalidateRect(self.raw_handle, None, true)
+                .if_null_to_error_else_drop(|| io::ErrorKind::Other.into())?;
+        }
+        Ok(())
+    }
+
+    /// Recalculates and immediately repaints the window frame (non-client area).
+    ///
+    /// Needed for changes made with [`Self::set_style`] or [`Self::set_extended_style`] to become visible.
+    pub fn redraw_frame(&self) -> io::Result<()> {
+        unsafe {
+            // Without this, the cached frame metrics would stay the same after style changes
+            SetWindowPos(
+                self.raw_handle,
+                None,
+                0,
+                0,
+                0,
+                0,
+                SWP_FRAMECHANGED | SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE,
+            )?;
+            RedrawWindow(
+                self.raw_handle,
+                None,
+                None,
+                RDW_FRAME | RDW_INVALIDATE | RDW_UPDATENOW,
+            )
+            .if_null_to_error_else_drop(|| io::ErrorKind::Other.into())?;
+        }
+        Ok(())
+    }
+
+    /// Returns the client area of the window in screen coordinates.
+    pub fn get_client_area_coords(&self) -> io::Result<Rectangle> {
+        let mut client_rect: RECT = Default::default();
+        unsafe { GetClientRect(self.raw_handle, &mut client_rect)? };
+        let mut top_left = Point {
+            x: client_rect.left,
+            y: client_rect.top,
+        };
+        let mut bottom_right = Point {
+            x: client_rect.right,
+            y: client_rect.bottom,
+        };
+        unsafe {
+            ClientToScreen(self.raw_handle, &mut top_left)
+                .if_null_to_error_else_drop(|| io::ErrorKind::Other.into())?;
+            ClientToScreen(self.raw_handle, &mut bottom_right)
+                .if_null_to_error_else_drop(|| io::ErrorKind::Other.into())?;
+        }
+        Ok(Rectangle {
+            left: top_left.x,
+            top: top_left.y,
+            right: bottom_right.x,
+            bottom: bottom_right.y,
+        })
+    }
+
+    /// Sets the shape of the window, or resets it to the default rectangular shape if `None` is given.
+    ///
+    /// The region is in window coordinates, relative to the upper-left corner of the whole window
+    /// including its frame.
+    pub fn set_region(&self, region: Option<Region>) -> io::Result<()> {
+        let raw_region = region.as_ref().map(HRGN::from).unwrap_or_default();
+        let result = unsafe { SetWindowRgn(self.raw_handle, raw_region, true) };
+        if result == 0 {
+            return Err(io::ErrorKind::Other.into());
+        }
+        // The system owns the region after success
+        let _ = region.map(Region::into_raw);
+        Ok(())
+    }
+
+    /// Returns a device context for drawing on the client area outside of paint messages.
+    pub fn get_dc(&self) -> io::Result<WindowDc<'_>> {
+        WindowDc::get(self)
+    }
+
+    /// Returns the DPI of the window, which depends on the monitor it is on
+    /// and the DPI awareness of the window.
+    ///
+    /// The default DPI (100% scaling) is 96.
+    pub fn get_dpi(&self) -> io::Result<u32> {
+        let dpi = unsafe { GetDpiForWindow(self.raw_handle) };
+        dpi.if_null_to_error(|| io::ErrorKind::NotFound.into())
+    }
+
+    /// Changes the parent window, returning the previous parent.
+    ///
+    /// If `None` is given, the desktop window becomes the new parent, which is also the previous parent
+    /// returned for top-level windows.
+    ///
+    /// The window style should be kept in sync with the parent: A window with a parent other than the desktop
+    /// should have the `WS_CHILD` style set, top-level windows should not.
+    pub fn set_parent(&self, new_parent: Option<&WindowHandle>) -> io::Result<WindowHandle> {
+        let new_parent = new_parent.map(HWND::from).unwrap_or_default();
+        let previous_parent = unsafe { SetParent(self.raw_handle, new_parent)? };
+        Ok(Self::from_non_null(previous_parent))
+    }
+
+    /// Associates a value with the window under the given key, using the window's property list.
+    ///
+    /// This is independent of the window's user data, which is used internally by [`Window`].
+    /// The value is stored directly in the property, so `T` cannot be larger than a pointer.
+    /// It should be plain data without padding such as an integer or a handle,
+    /// since the stored bits are reinterpreted as `T` by [`Self::get_prop`].
+    pub fn set_prop<T: Copy>(&self, key: &str, value: T) -> io::Result<()> {
+        let bits = prop_value_to_bits(value);
+        unsafe {
+            SetPropW(
+                self.raw_handle,
+                PCWSTR::from_raw(key.to_wide_string().as_ptr()),
+                HANDLE(bits as *mut c_void),
+            )?;
+        }
+        // A stored 0 is indistinguishable from a missing property, so its presence is recorded separately
+        let zero_marker_key = get_zero_prop_marker_key(key).to_wide_string();
+        if bits == 0 {
+            unsafe {
+                SetPropW(
+                    self.raw_handle,
+                    PCWSTR::from_raw(zero_marker_key.as_ptr()),
+                    HANDLE(usize::MAX as *mut c_void),
+                )?;
+            }
+        } else {
+            let _ =
+                unsafe { RemovePropW(self.raw_handle, PCWSTR::from_raw(zero_marker_key.as_ptr())) };
+        }
+        Ok(())
+    }
+
+    /// Returns the value associated with the key by [`Self::set_prop`], if any.
+    pub fn get_prop<T: Copy>(&self, key: &str) -> Option<T> {
+        let bits = self.get_raw_prop(key);
+        (bits != 0 || self.get_raw_prop(&get_zero_prop_marker_key(key)) != 0)
+            .then(|| prop_value_from_bits(bits))
+    }
+
+    /// Removes the value associated with the key by [`Self::set_prop`], returning it.
+    pub fn remove_prop<T: Copy>(&self, key: &str) -> Option<T> {
+        let value = self.get_prop(key)?;
+        for key in [key.to_owned(), get_zero_prop_marker_key(key)] {
+            let _ = unsafe {
+                RemovePropW(
+                    self.raw_handle,
+                    PCWSTR::from_raw(key.to_wide_string().as_ptr()),
+                )
+            };
+        }
+        Some(value)
+    }
+
+    fn get_raw_prop(&self, key: &str) -> usize {
+        let value = unsafe {
+            GetPropW(
+                self.raw_handle,
+                PCWSTR::from_raw(key.to_wide_string().as_ptr()),
+            )
+        };
+        value.0 as usize
+    }
+
     /// Returns the window's show state and positions.
     pub fn get_placement(&self) -> io::Result<WindowPlacement> {
         let mut raw_placement: WINDOWPLACEMENT = WINDOWPLACEMENT {
@@ -330,6 +971,111 @@ impl WindowHandle {
         Ok(())
     }
 
+    /// Moves and resizes the window. The area is in screen coordinates for top-level windows
+    /// and in client coordinates of the parent window for child windows.
+    ///
+    /// The Z-order and activation state of the window are not changed.
+    pub fn set_position(&self, area: Rectangle) -> io::Result<()> {
+        unsafe {
+            SetWindowPos(
+                self.raw_handle,
+                None,
+                area.left,
+                area.top,
+                area.right - area.left,
+                area.bottom - area.top,
+                SWP_NOZORDER | SWP_NOACTIVATE,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Moves the window to a part of the work area of the monitor it is currently on,
+    /// similar to the snapping done by Windows.
+    ///
+    /// Minimized or maximized windows are restored first, except for [`SnapRegion::Maximize`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use winapi_easy::ui::{
+    ///     SnapRegion,
+    ///     WindowHandle,
+    /// };
+    ///
+    /// if let Some(window) = WindowHandle::get_foreground_window() {
+    ///     window.snap(SnapRegion::LeftHalf)?;
+    /// }
+    ///
+    /// # Result::<(), std::io::Error>::Ok(())
+    /// ```
+    pub fn snap(&self, region: SnapRegion) -> io::Result<()> {
+        if region == SnapRegion::Maximize {
+            return self.set_show_state(WindowShowState::Maximize);
+        }
+        if unsafe { IsZoomed(self.raw_handle).as_bool() || IsIconic(self.raw_handle).as_bool() } {
+            self.set_show_state(WindowShowState::Restore)?;
+        }
+        let work_area = desktop::MonitorHandle::from_window(self).info()?.work_area;
+        let mut target_area = region.get_area(work_area);
+        // The window rect includes invisible resize borders, which would otherwise leave gaps
+        let mut window_rect: Rectangle = Default::default();
+        unsafe { GetWindowRect(self.raw_handle, &mut window_rect)? };
+        let mut visible_rect: Rectangle = window_rect;
+        let _ = unsafe {
+            DwmGetWindowAttribute(
+                self.raw_handle,
+                DWMWA_EXTENDED_FRAME_BOUNDS,
+                &mut visible_rect as *mut Rectangle as *mut c_void,
+                mem::size_of::<Rectangle>().try_into().unwrap(),
+            )
+        };
+        target_area.left -= visible_rect.left - window_rect.left;
+        target_area.top -= visible_rect.top - window_rect.top;
+        target_area.right += window_rect.right - visible_rect.right;
+        target_area.bottom += window_rect.bottom - visible_rect.bottom;
+        self.set_position(target_area)
+    }
+
+    /// Moves the window to the work area of another monitor.
+    ///
+    /// Maximized windows stay maximized on the target monitor, minimized windows are restored first.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use winapi_easy::ui::desktop::MonitorHandle;
+    /// use winapi_easy::ui::{
+    ///     MoveMode,
+    ///     WindowHandle,
+    /// };
+    ///
+    /// if let Some(window) = WindowHandle::get_foreground_window() {
+    ///     window.move_to_monitor(&MonitorHandle::get_primary(), MoveMode::PreserveRelative)?;
+    /// }
+    ///
+    /// # Result::<(), std::io::Error>::Ok(())
+    /// ```
+    pub fn move_to_monitor(
+        &self,
+        monitor: &desktop::MonitorHandle,
+        mode: MoveMode,
+    ) -> io::Result<()> {
+        let was_maximized = unsafe { IsZoomed(self.raw_handle).as_bool() };
+        if was_maximized || unsafe { IsIconic(self.raw_handle).as_bool() } {
+            self.set_show_state(WindowShowState::Restore)?;
+        }
+        let source_work_area = desktop::MonitorHandle::from_window(self).info()?.work_area;
+        let target_work_area = monitor.info()?.work_area;
+        let mut window_rect: Rectangle = Default::default();
+        unsafe { GetWindowRect(self.raw_handle, &mut window_rect)? };
+        self.set_position(mode.get_area(window_rect, source_work_area, target_work_area))?;
+        if was_maximized {
+            self.set_show_state(WindowShowState::Maximize)?;
+        }
+        Ok(())
+    }
+
     /// Returns the class name of the window's associated [`WindowClass`].
     pub fn get_class_name(&self) -> io::Result<String> {
         const BUFFER_SIZE: usize = WindowClass::MAX_WINDOW_CLASS_NAME_CHARS + 1;
@@ -354,6 +1100,41 @@ impl WindowHandle {
             .if_non_null_to_error(|| custom_err_with_code("Cannot perform window action", result.0))
     }
 
+    /// Sends a message to the window, waiting at most for the given timeout for it to be processed.
+    ///
+    /// Returns the result of the message processing, or `None` if the timeout elapsed
+    /// or the window is hung when using [`SendTimeoutFlags::AbortIfHung`].
+    pub fn send_message_timeout(
+        &self,
+        message: u32,
+        w_param: usize,
+        l_param: isize,
+        timeout: Duration,
+        flags: SendTimeoutFlags,
+    ) -> io::Result<Option<isize>> {
+        let timeout_ms = timeout.as_millis().try_into().unwrap_or(u32::MAX);
+        let mut result: usize = 0;
+        let ret_val = unsafe {
+            SendMessageTimeoutW(
+                self.raw_handle,
+                message,
+                WPARAM(w_param),
+                LPARAM(l_param),
+                flags.into(),
+                timeout_ms,
+                Some(&mut result),
+            )
+        };
+        if ret_val.0 == 0 {
+            let err_val = unsafe { GetLastError() };
+            if err_val == ERROR_TIMEOUT || err_val == NO_ERROR {
+                return Ok(None);
+            }
+            return Err(io::Error::from_raw_os_error(err_val.0 as i32));
+        }
+        Ok(Some(result as isize))
+    }
+
     /// Flashes the window using default flash settings.
     ///
     /// Same as [`Self::flash_custom`] using [`Default::default`] for all parameters.
@@ -418,20 +1199,98 @@ impl WindowHandle {
         self.get_creator_thread_process_ids().1
     }
 
+    /// Returns the integrity level of the process that created this window.
+    ///
+    /// Input sent to windows of processes with a higher integrity level than the current one is silently discarded.
     #[cfg(feature = "process")]
-    fn get_creator_thread_process_ids(&self) -> (ThreadId, ProcessId) {
-        use windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId;
-        let mut process_id: u32 = 0;
-        let thread_id = unsafe { GetWindowThreadProcessId(self.raw_handle, Some(&mut process_id)) };
-        (ThreadId(thread_id), ProcessId(process_id))
+    pub fn get_integrity_level(&self) -> io::Result<IntegrityLevel> {
+        self.get_creator_process_id().get_integrity_level()
     }
 
-    /// Returns all top-level (non-child) windows created by the thread.
-    #[cfg(feature = "process")]
-    pub fn get_nonchild_windows(thread_id: ThreadId) -> Vec<Self> {
-        use windows::Win32::UI::WindowsAndMessaging::EnumThreadWindows;
-        let mut result: Vec<WindowHandle> = Vec::new();
-        let mut callback = |handle: HWND, _app_value: LPARAM| -> BOOL {
+    /// Returns the icon of the application the window belongs to, e.g. for display in a window switcher.
+    ///
+    /// Tries the icon of the window, the icon of its class and the first icon of the process's executable file,
+    /// falling back to the default application icon.
+    pub fn get_app_icon(&self) -> io::Result<OwnedIcon> {
+        const TIMEOUT_MS: u32 = 100;
+        for icon_type in [ICON_BIG, ICON_SMALL2] {
+            let mut raw_icon: usize = 0;
+            // Using a timeout since a hung window would otherwise block forever
+            let _ = unsafe {
+                SendMessageTimeoutW(
+                    self.raw_handle,
+                    WM_GETICON,
+                    WPARAM(icon_type as usize),
+                    LPARAM::default(),
+                    SMTO_ABORTIFHUNG,
+                    TIMEOUT_MS,
+                    Some(&mut raw_icon),
+                )
+            };
+            if raw_icon != 0 {
+                return OwnedIcon::copy_from_handle(HICON(raw_icon as *mut c_void));
+            }
+        }
+        let raw_class_icon = unsafe { GetClassLongPtrW(self.raw_handle, GCLP_HICON) };
+        if raw_class_icon != 0 {
+            return OwnedIcon::copy_from_handle(HICON(raw_class_icon as *mut c_void));
+        }
+        #[cfg(feature = "process")]
+        if let Ok(executable_path) = self.get_creator_process_id().get_executable_path() {
+            use windows::Win32::UI::Shell::ExtractIconExW;
+            let executable_path = ZeroTerminatedWideString::from_os_str(executable_path);
+            let mut raw_icon = HICON::default();
+            let icon_count = unsafe {
+                ExtractIconExW(
+                    executable_path.as_raw_pcwstr(),
+                    0,
+                    Some(&mut raw_icon),
+                    None,
+                    1,
+                )
+            };
+            if icon_count > 0 && !raw_icon.is_invalid() {
+                return Ok(OwnedIcon::from_non_shared_handle(raw_icon));
+            }
+        }
+        OwnedIcon::copy_from_handle(BuiltinIcon::Application.as_handle()?)
+    }
+
+    #[cfg(feature = "process")]
+    fn get_creator_thread_process_ids(&self) -> (ThreadId, ProcessId) {
+        use windows::Win32::UI::WindowsAndMessaging::GetWindowThreadProcessId;
+        let mut process_id: u32 = 0;
+        let thread_id = unsafe { GetWindowThreadProcessId(self.raw_handle, Some(&mut process_id)) };
+        (ThreadId(thread_id), ProcessId(process_id))
+    }
+
+    /// Returns all top-level windows created by the process.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use winapi_easy::process::Process;
+    /// use winapi_easy::ui::WindowHandle;
+    ///
+    /// let windows = WindowHandle::get_process_windows(Process::current().get_id())?;
+    ///
+    /// # Result::<(), std::io::Error>::Ok(())
+    /// ```
+    #[cfg(feature = "process")]
+    pub fn get_process_windows(process_id: ProcessId) -> io::Result<Vec<Self>> {
+        let result = Self::get_toplevel_windows()?
+            .into_iter()
+            .filter(|window| window.get_creator_process_id() == process_id)
+            .collect();
+        Ok(result)
+    }
+
+    /// Returns all top-level (non-child) windows created by the thread.
+    #[cfg(feature = "process")]
+    pub fn get_nonchild_windows(thread_id: ThreadId) -> Vec<Self> {
+        use windows::Win32::UI::WindowsAndMessaging::EnumThreadWindows;
+        let mut result: Vec<WindowHandle> = Vec::new();
+        let mut callback = |handle: HWND, _app_value: LPARAM| -> BOOL {
             let window_handle =
                 WindowHandle::from_maybe_null(handle).expect("Window handle should not be null");
             result.push(window_handle);
@@ -463,6 +1322,102 @@ impl WindowHandle {
         })
     }
 
+    /// Sets whether the window accepts files dropped onto it using drag & drop.
+    ///
+    /// Dropped files are passed to [`WindowMessageListener::handle_files_dropped`].
+    pub fn set_accept_files(&self, accept: bool) {
+        unsafe { DragAcceptFiles(self.raw_handle, accept) };
+    }
+
+    /// Returns various window metrics in a single call.
+    pub fn get_info(&self) -> io::Result<WindowInfo> {
+        let mut raw_info = WINDOWINFO {
+            cbSize: mem::size_of::<WINDOWINFO>().try_into().unwrap(),
+            ..Default::default()
+        };
+        unsafe { GetWindowInfo(self.raw_handle, &mut raw_info)? };
+        Ok(WindowInfo {
+            window_area: raw_info.rcWindow,
+            client_area: raw_info.rcClient,
+            style: WindowStyle::from(raw_info.dwStyle.0),
+            extended_style: WindowExtendedStyle::from(raw_info.dwExStyle.0),
+            is_active: raw_info.dwWindowStatus == WS_ACTIVECAPTION.0,
+            border_width: raw_info.cxWindowBorders,
+            border_height: raw_info.cyWindowBorders,
+        })
+    }
+
+    pub fn get_style(&self) -> WindowStyle {
+        let raw_style = unsafe { GetWindowLongPtrW(self.raw_handle, GWL_STYLE) };
+        WindowStyle::from(raw_style as u32)
+    }
+
+    /// Replaces the window style.
+    ///
+    /// Some style changes only take effect after the window frame is redrawn, see [`Self::redraw_frame`].
+    pub fn set_style(&self, style: WindowStyle) -> io::Result<()> {
+        self.set_window_long_ptr(GWL_STYLE, u32::from(style) as isize)
+    }
+
+    pub fn get_extended_style(&self) -> WindowExtendedStyle {
+        let raw_style = unsafe { GetWindowLongPtrW(self.raw_handle, GWL_EXSTYLE) };
+        WindowExtendedStyle::from(raw_style as u32)
+    }
+
+    /// Replaces the extended window style.
+    ///
+    /// Some style changes only take effect after the window frame is redrawn, see [`Self::redraw_frame`].
+    pub fn set_extended_style(&self, style: WindowExtendedStyle) -> io::Result<()> {
+        self.set_window_long_ptr(GWL_EXSTYLE, u32::from(style) as isize)
+    }
+
+    /// Makes the window a tool window or a regular application window.
+    ///
+    /// Tool windows are not shown in the taskbar or the Alt+Tab window switcher.
+    /// For visible windows, the taskbar will only reflect the change after the window was hidden and shown again.
+    pub fn set_tool_window(&self, is_tool_window: bool) -> io::Result<()> {
+        let raw_style = u32::from(self.get_extended_style());
+        let tool_window = u32::from(WindowExtendedStyle::ToolWindow);
+        let app_window = u32::from(WindowExtendedStyle::AppWindow);
+        let new_raw_style = if is_tool_window {
+            (raw_style | tool_window) & !app_window
+        } else {
+            raw_style & !tool_window
+        };
+        self.set_extended_style(WindowExtendedStyle::from(new_raw_style))
+    }
+
+    /// Sets the opacity of the whole window, from fully transparent (`0`) to opaque (`255`).
+    ///
+    /// As a side effect, this adds the [`WindowExtendedStyle::Layered`] style to the window if it is not set already.
+    /// This also works for windows of other processes.
+    pub fn set_opacity(&self, alpha: u8) -> io::Result<()> {
+        let raw_style = u32::from(self.get_extended_style());
+        let layered = u32::from(WindowExtendedStyle::Layered);
+        if raw_style & layered == 0 {
+            self.set_extended_style(WindowExtendedStyle::from(raw_style | layered))?;
+        }
+        unsafe {
+            SetLayeredWindowAttributes(self.raw_handle, COLORREF::default(), alpha, LWA_ALPHA)?
+        };
+        Ok(())
+    }
+
+    fn set_window_long_ptr(&self, index: WINDOW_LONG_PTR_INDEX, value: isize) -> io::Result<()> {
+        unsafe {
+            // A return value of 0 is only an error if the last error was set
+            SetLastError(NO_ERROR);
+            let ret_val = SetWindowLongPtrW(self.raw_handle, index, value);
+            if ret_val == 0 {
+                let err_val = GetLastError();
+                if err_val != NO_ERROR {
+                    return Err(io::Error::from_raw_os_error(err_val.0 as i32));
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) unsafe fn get_user_data_ptr<T>(&self) -> Option<NonNull<T>> {
         let ptr_value = GetWindowLongPtrW(self.raw_handle, GWLP_USERDATA);
         NonNull::new(ptr_value as *mut T)
@@ -538,9 +1493,12 @@ impl<'res, WML: WindowMessageListener> WindowClass<'res, WML> {
     /// The class name will be generated from the given prefix by adding a random base64 encoded UUID
     /// to ensure uniqueness. This means that the maximum length of the class name prefix is a little less
     /// than the standard 256 characters for class names.
+    ///
+    /// Class styles can be combined using `|`, e.g. `WindowClassStyle::DoubleClicks | WindowClassStyle::DropShadow`.
     pub fn register_new<B, I, C>(
         class_name_prefix: &str,
         appearance: WindowClassAppearance<B, I, C>,
+        class_style: Option<WindowClassStyle>,
     ) -> io::Result<Self>
     where
         B: Brush + 'res,
@@ -562,6 +1520,7 @@ impl<'res, WML: WindowMessageListener> WindowClass<'res, WML> {
         // No need to reserve extra window memory if we only need a single pointer
         let class_def = WNDCLASSEXW {
             cbSize: mem::size_of::<WNDCLASSEXW>().try_into().unwrap(),
+            style: class_style.map(Into::into).unwrap_or_default(),
             lpfnWndProc: Some(generic_window_proc::<WML>),
             hIcon: icon_handle,
             hCursor: appearance
@@ -620,6 +1579,148 @@ impl Default for WindowClassAppearance<BuiltinColor, BuiltinIcon, BuiltinCursor>
     }
 }
 
+/// Class styles for [`WindowClass::register_new`].
+#[derive(IntoPrimitive, FromPrimitive, Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(u32)]
+pub enum WindowClassStyle {
+    /// Windows of the class receive double-click messages.
+    DoubleClicks = CS_DBLCLKS.0,
+    /// Enables a drop shadow effect, mostly useful for short-lived popup windows.
+    DropShadow = CS_DROPSHADOW.0,
+    /// Allocates a private device context for each window of the class.
+    OwnDeviceContext = CS_OWNDC.0,
+    /// Redraws the entire window if a movement or size adjustment changes the width of the client area.
+    HorizontalRedraw = CS_HREDRAW.0,
+    /// Redraws the entire window if a movement or size adjustment changes the height of the client area.
+    VerticalRedraw = CS_VREDRAW.0,
+    /// Disables the close command in the window menu.
+    NoClose = CS_NOCLOSE.0,
+    #[num_enum(catch_all)]
+    Other(u32),
+}
+
+impl BitOr for WindowClassStyle {
+    type Output = WindowClassStyle;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self::from(u32::from(self) | u32::from(rhs))
+    }
+}
+
+impl BitOrAssign for WindowClassStyle {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs
+    }
+}
+
+impl From<WindowClassStyle> for WNDCLASS_STYLES {
+    fn from(value: WindowClassStyle) -> Self {
+        WNDCLASS_STYLES(value.into())
+    }
+}
+
+/// Window styles.
+///
+/// See also: [Microsoft docs](https://learn.microsoft.com/en-us/windows/win32/winmsg/window-styles)
+#[derive(IntoPrimitive, FromPrimitive, Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(u32)]
+pub enum WindowStyle {
+    /// A regular top-level window with a title bar, a window menu, a sizing border and minimize and maximize buttons.
+    OverlappedWindow = WS_OVERLAPPEDWINDOW.0,
+    /// A pop-up window, often used without any other style for borderless windows.
+    Popup = WS_POPUP.0,
+    /// A thin-line border.
+    Border = WS_BORDER.0,
+    /// A title bar, includes [`Self::Border`].
+    Caption = WS_CAPTION.0,
+    /// A window menu in the title bar, requires [`Self::Caption`].
+    SysMenu = WS_SYSMENU.0,
+    /// A sizing border.
+    ThickFrame = WS_THICKFRAME.0,
+    /// A minimize button in the title bar, requires [`Self::SysMenu`].
+    MinimizeBox = WS_MINIMIZEBOX.0,
+    /// A maximize button in the title bar, requires [`Self::SysMenu`].
+    MaximizeBox = WS_MAXIMIZEBOX.0,
+    /// A window that cannot receive user input.
+    Disabled = WS_DISABLED.0,
+    Child = WS_CHILD.0,
+    Visible = WS_VISIBLE.0,
+    Minimize = WS_MINIMIZE.0,
+    Maximize = WS_MAXIMIZE.0,
+    /// Excludes the area occupied by child windows when drawing in the parent window.
+    ClipChildren = WS_CLIPCHILDREN.0,
+    /// Excludes the area occupied by sibling child windows when drawing in a child window.
+    ClipSiblings = WS_CLIPSIBLINGS.0,
+    #[num_enum(catch_all)]
+    Other(u32),
+}
+
+impl BitOr for WindowStyle {
+    type Output = WindowStyle;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self::from(u32::from(self) | u32::from(rhs))
+    }
+}
+
+impl BitOrAssign for WindowStyle {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs
+    }
+}
+
+impl From<WindowStyle> for WINDOW_STYLE {
+    fn from(value: WindowStyle) -> Self {
+        WINDOW_STYLE(value.into())
+    }
+}
+
+/// Extended window styles.
+///
+/// See also: [Microsoft docs](https://learn.microsoft.com/en-us/windows/win32/winmsg/extended-window-styles)
+#[derive(IntoPrimitive, FromPrimitive, Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(u32)]
+pub enum WindowExtendedStyle {
+    /// Forces a top-level window onto the taskbar when it is visible.
+    AppWindow = WS_EX_APPWINDOW.0,
+    /// A window that is not shown in the taskbar or the Alt+Tab window switcher.
+    ToolWindow = WS_EX_TOOLWINDOW.0,
+    /// A border with a sunken edge.
+    ClientEdge = WS_EX_CLIENTEDGE.0,
+    /// The window accepts files dropped onto it using drag & drop.
+    AcceptFiles = WS_EX_ACCEPTFILES.0,
+    /// The window is placed above all non-topmost windows.
+    TopMost = WS_EX_TOPMOST.0,
+    /// A window that does not become the foreground window when clicked.
+    NoActivate = WS_EX_NOACTIVATE.0,
+    /// A layered window, allowing transparency effects.
+    Layered = WS_EX_LAYERED.0,
+    /// Mouse input passes through to the windows below, if combined with [`Self::Layered`].
+    Transparent = WS_EX_TRANSPARENT.0,
+    #[num_enum(catch_all)]
+    Other(u32),
+}
+
+impl BitOr for WindowExtendedStyle {
+    type Output = WindowExtendedStyle;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self::from(u32::from(self) | u32::from(rhs))
+    }
+}
+
+impl BitOrAssign for WindowExtendedStyle {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs
+    }
+}
+
+impl From<WindowExtendedStyle> for WINDOW_EX_STYLE {
+    fn from(value: WindowExtendedStyle) -> Self {
+        WINDOW_EX_STYLE(value.into())
+    }
+}
+
 /// A window based on a [`WindowClass`].
 #[derive(Debug)]
 pub struct Window<'class, 'listener, WML> {
@@ -636,18 +1737,39 @@ impl<'class, 'listener, WML: WindowMessageListener> Window<'class, 'listener, WM
         class: &'class WindowClass<WML>,
         listener: &'listener WML,
         window_name: &str,
+    ) -> io::Result<Self> {
+        Self::create_with_parent(class, listener, window_name, WS_OVERLAPPEDWINDOW, None)
+    }
+
+    /// Creates a new message-only window.
+    ///
+    /// This kind of window is never visible, cannot be enumerated and does not receive broadcast messages.
+    /// It is useful for receiving messages, e.g. for notification icons or change notifications.
+    pub fn create_new_message_only(
+        class: &'class WindowClass<WML>,
+        listener: &'listener WML,
+    ) -> io::Result<Self> {
+        Self::create_with_parent(class, listener, "", Default::default(), Some(HWND_MESSAGE))
+    }
+
+    fn create_with_parent(
+        class: &'class WindowClass<WML>,
+        listener: &'listener WML,
+        window_name: &str,
+        style: WINDOW_STYLE,
+        parent: Option<HWND>,
     ) -> io::Result<Self> {
         let h_wnd: HWND = unsafe {
             CreateWindowExW(
                 Default::default(),
                 PCWSTR(class.atom as *const u16),
                 PCWSTR::from_raw(window_name.to_wide_string().as_ptr()),
-                WS_OVERLAPPEDWINDOW,
+                style,
                 CW_USEDEFAULT,
                 0,
                 CW_USEDEFAULT,
                 0,
-                None,
+                parent.unwrap_or_default(),
                 None,
                 None,
                 None,
@@ -664,6 +1786,38 @@ impl<'class, 'listener, WML: WindowMessageListener> Window<'class, 'listener, WM
         })
     }
 
+    /// Takes ownership of an existing window, so that it will be destroyed when the returned [`Window`] is dropped.
+    ///
+    /// The window must be an instance of the given class, e.g. created via `CreateWindowExW` with the class name
+    /// from [`WindowHandle::get_class_name`]. Messages will be sent to the given listener from now on.
+    ///
+    /// # Safety
+    ///
+    /// The window must have been created by the current thread and must not be destroyed by anything else,
+    /// including other [`Window`] instances.
+    pub unsafe fn from_handle_owned(
+        handle: WindowHandle,
+        class: &'class WindowClass<WML>,
+        listener: &'listener WML,
+    ) -> io::Result<Self> {
+        let class_atom = unsafe { GetClassLongW(handle.raw_handle, GCW_ATOM) };
+        class_atom.if_null_get_last_error()?;
+        if class_atom != u32::from(class.atom) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Window is not an instance of the given class",
+            ));
+        }
+        unsafe {
+            handle.set_user_data_ptr(listener)?;
+        }
+        Ok(Window {
+            class,
+            handle,
+            phantom: PhantomData,
+        })
+    }
+
     /// Changes the [`WindowMessageListener`].
     ///
     /// Doing this is likely only possible using a [`WindowMessageListener`] that doesn't contain any closures
@@ -690,9 +1844,9 @@ impl<'class, 'listener, WML: WindowMessageListener> Window<'class, 'listener, WM
         let call_data = get_notification_call_data(
             &self.handle,
             options.icon_id,
-            true,
+            Some(options.version),
             Some(chosen_icon_handle),
-            options.tooltip_text,
+            options.tooltip_text.map(|text| (text, true)),
             Some(!options.visible),
             None,
         );
@@ -753,17 +1907,270 @@ pub enum WindowShowState {
     ShowNormal = SW_SHOWNORMAL.0,
 }
 
+/// An animation for [`WindowHandle::animate_show`] and [`WindowHandle::animate_hide`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum AnimateEffect {
+    /// Slides the whole window in or out.
+    Slide(AnimateDirection),
+    /// Uncovers or covers the window progressively, without moving it.
+    Roll(AnimateDirection),
+    /// Expands the window outwards from its center or collapses it inwards.
+    Center,
+    /// Fades the window in or out. Only works with top-level windows.
+    Fade,
+}
+
+impl From<AnimateEffect> for ANIMATE_WINDOW_FLAGS {
+    fn from(value: AnimateEffect) -> Self {
+        match value {
+            AnimateEffect::Slide(direction) => AW_SLIDE | direction.into(),
+            AnimateEffect::Roll(direction) => direction.into(),
+            AnimateEffect::Center => AW_CENTER,
+            AnimateEffect::Fade => AW_BLEND,
+        }
+    }
+}
+
+/// Direction of an [`AnimateEffect`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum AnimateDirection {
+    LeftToRight,
+    RightToLeft,
+    TopToBottom,
+    BottomToTop,
+}
+
+impl From<AnimateDirection> for ANIMATE_WINDOW_FLAGS {
+    fn from(value: AnimateDirection) -> Self {
+        match value {
+            AnimateDirection::LeftToRight => AW_HOR_POSITIVE,
+            AnimateDirection::RightToLeft => AW_HOR_NEGATIVE,
+            AnimateDirection::TopToBottom => AW_VER_POSITIVE,
+            AnimateDirection::BottomToTop => AW_VER_NEGATIVE,
+        }
+    }
+}
+
+/// A part of a monitor's work area for [`WindowHandle::snap`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum SnapRegion {
+    LeftHalf,
+    RightHalf,
+    TopHalf,
+    BottomHalf,
+    TopLeftQuarter,
+    TopRightQuarter,
+    BottomLeftQuarter,
+    BottomRightQuarter,
+    /// Maximizes the window.
+    Maximize,
+}
+
+impl SnapRegion {
+    fn get_area(self, work_area: Rectangle) -> Rectangle {
+        let center_x = work_area.left + (work_area.right - work_area.left) / 2;
+        let center_y = work_area.top + (work_area.bottom - work_area.top) / 2;
+        let (left, right) = match self {
+            SnapRegion::LeftHalf | SnapRegion::TopLeftQuarter | SnapRegion::BottomLeftQuarter => {
+                (work_area.left, center_x)
+            }
+            SnapRegion::RightHalf
+            | SnapRegion::TopRightQuarter
+            | SnapRegion::BottomRightQuarter => (center_x, work_area.right),
+            SnapRegion::TopHalf | SnapRegion::BottomHalf | SnapRegion::Maximize => {
+                (work_area.left, work_area.right)
+            }
+        };
+        let (top, bottom) = match self {
+            SnapRegion::TopHalf | SnapRegion::TopLeftQuarter | SnapRegion::TopRightQuarter => {
+                (work_area.top, center_y)
+            }
+            SnapRegion::BottomHalf
+            | SnapRegion::BottomLeftQuarter
+            | SnapRegion::BottomRightQuarter => (center_y, work_area.bottom),
+            SnapRegion::LeftHalf | SnapRegion::RightHalf | SnapRegion::Maximize => {
+                (work_area.top, work_area.bottom)
+            }
+        };
+        Rectangle {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+}
+
+/// How to position a window on another monitor with [`WindowHandle::move_to_monitor`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum MoveMode {
+    /// Keeps the position and size relative to the work area, scaling them if the work area sizes differ.
+    PreserveRelative,
+    /// Keeps the size as far as it fits and centers the window in the work area.
+    Center,
+}
+
+impl MoveMode {
+    fn get_area(
+        self,
+        window_rect: Rectangle,
+        source_work_area: Rectangle,
+        target_work_area: Rectangle,
+    ) -> Rectangle {
+        let source_width = source_work_area.right - source_work_area.left;
+        let source_height = source_work_area.bottom - source_work_area.top;
+        let target_width = target_work_area.right - target_work_area.left;
+        let target_height = target_work_area.bottom - target_work_area.top;
+        match self {
+            MoveMode::PreserveRelative => {
+                let scale = |offset: i32, source_len: i32, target_len: i32| -> i32 {
+                    if source_len <= 0 {
+                        return offset;
+                    }
+                    let scaled = i64::from(offset) * i64::from(target_len) / i64::from(source_len);
+                    scaled.try_into().unwrap_or(offset)
+                };
+                Rectangle {
+                    left: target_work_area.left
+                        + scale(
+                            window_rect.left - source_work_area.left,
+                            source_width,
+                            target_width,
+                        ),
+                    top: target_work_area.top
+                        + scale(
+                            window_rect.top - source_work_area.top,
+                            source_height,
+                            target_height,
+                        ),
+                    right: target_work_area.left
+                        + scale(
+                            window_rect.right - source_work_area.left,
+                            source_width,
+                            target_width,
+                        ),
+                    bottom: target_work_area.top
+                        + scale(
+                            window_rect.bottom - source_work_area.top,
+                            source_height,
+                            target_height,
+                        ),
+                }
+            }
+            MoveMode::Center => {
+                let width = (window_rect.right - window_rect.left).min(target_width);
+                let height = (window_rect.bottom - window_rect.top).min(target_height);
+                let left = target_work_area.left + (target_width - width) / 2;
+                let top = target_work_area.top + (target_height - height) / 2;
+                Rectangle {
+                    left,
+                    top,
+                    right: left + width,
+                    bottom: top + height,
+                }
+            }
+        }
+    }
+}
+
 impl From<WindowShowState> for SHOW_WINDOW_CMD {
     fn from(value: WindowShowState) -> Self {
         SHOW_WINDOW_CMD(value.into())
     }
 }
 
+/// Window metrics returned by [`WindowHandle::get_info`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct WindowInfo {
+    /// Area of the whole window in screen coordinates.
+    pub window_area: Rectangle,
+    /// Client area in screen coordinates.
+    pub client_area: Rectangle,
+    pub style: WindowStyle,
+    pub extended_style: WindowExtendedStyle,
+    /// Whether the window is active, i.e. has an active caption.
+    pub is_active: bool,
+    pub border_width: u32,
+    pub border_height: u32,
+}
+
 /// DPI-scaled virtual coordinates.
 pub type Point = POINT;
 /// DPI-scaled virtual coordinates of a rectangle.
 pub type Rectangle = RECT;
 
+/// Criteria for finding a window, see [`WindowHandle::wait_for`].
+///
+/// Criteria set to `None` match any window.
+#[derive(Copy, Clone, Eq, PartialEq, Default, Debug)]
+pub struct WindowMatcher<'a> {
+    /// The window class name, compared case-insensitively.
+    pub class_name: Option<&'a str>,
+    /// Text that the window caption must contain.
+    pub caption_contains: Option<&'a str>,
+}
+
+impl WindowMatcher<'_> {
+    /// Checks whether the window matches all criteria.
+    pub fn matches(&self, window: &WindowHandle) -> bool {
+        let class_matches = self.class_name.is_none_or(|class_name| {
+            window
+                .get_class_name()
+                .is_ok_and(|actual| actual.eq_ignore_ascii_case(class_name))
+        });
+        let caption_matches = self
+            .caption_contains
+            .is_none_or(|text| window.get_caption_text().contains(text));
+        class_matches && caption_matches
+    }
+}
+
+/// Conversion of coordinates between different DPI values, e.g. from logical to physical coordinates.
+pub trait DpiScaleExt: Copy {
+    /// Scales the coordinates from one DPI value to another, rounding to the nearest integer.
+    ///
+    /// The DPI value corresponding to 100% scaling is 96.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if `from_dpi` is zero.
+    fn scale_for_dpi(self, from_dpi: u32, to_dpi: u32) -> Self;
+}
+
+impl DpiScaleExt for Point {
+    fn scale_for_dpi(self, from_dpi: u32, to_dpi: u32) -> Self {
+        Point {
+            x: scale_coord_for_dpi(self.x, from_dpi, to_dpi),
+            y: scale_coord_for_dpi(self.y, from_dpi, to_dpi),
+        }
+    }
+}
+
+impl DpiScaleExt for Rectangle {
+    fn scale_for_dpi(self, from_dpi: u32, to_dpi: u32) -> Self {
+        Rectangle {
+            left: scale_coord_for_dpi(self.left, from_dpi, to_dpi),
+            top: scale_coord_for_dpi(self.top, from_dpi, to_dpi),
+            right: scale_coord_for_dpi(self.right, from_dpi, to_dpi),
+            bottom: scale_coord_for_dpi(self.bottom, from_dpi, to_dpi),
+        }
+    }
+}
+
+fn scale_coord_for_dpi(coord: i32, from_dpi: u32, to_dpi: u32) -> i32 {
+    assert_ne!(from_dpi, 0, "Source DPI must not be zero");
+    let numerator = i64::from(coord) * i64::from(to_dpi);
+    let divisor = i64::from(from_dpi);
+    // Rounds halfway cases away from zero
+    let half = if numerator < 0 {
+        -divisor / 2
+    } else {
+        divisor / 2
+    };
+    let scaled = (numerator + half) / divisor;
+    i32::try_from(scaled).unwrap_or(if scaled < 0 { i32::MIN } else { i32::MAX })
+}
+
 /// Window show state plus positions.
 #[derive(Copy, Clone, Debug)]
 pub struct WindowPlacement {
@@ -806,6 +2213,114 @@ impl WindowPlacement {
     pub fn set_restored_position(&mut self, rectangle: Rectangle) {
         self.raw_placement.rcNormalPosition = rectangle;
     }
+
+    const SERIALIZATION_VERSION: u8 = 1;
+    const SERIALIZED_LEN_V1: usize = 1 + 10 * mem::size_of::<u32>();
+
+    /// Serializes the placement, e.g. for saving window layouts to a config file.
+    ///
+    /// The data starts with a version tag, so that data from older versions can still be read
+    /// by [`Self::from_bytes`] if fields are added.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let raw = &self.raw_placement;
+        let values: [u32; 10] = [
+            raw.flags.0,
+            raw.showCmd,
+            raw.ptMinPosition.x as u32,
+            raw.ptMinPosition.y as u32,
+            raw.ptMaxPosition.x as u32,
+            raw.ptMaxPosition.y as u32,
+            raw.rcNormalPosition.left as u32,
+            raw.rcNormalPosition.top as u32,
+            raw.rcNormalPosition.right as u32,
+            raw.rcNormalPosition.bottom as u32,
+        ];
+        let mut bytes = Vec::with_capacity(Self::SERIALIZED_LEN_V1);
+        bytes.push(Self::SERIALIZATION_VERSION);
+        for value in values {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Deserializes a placement created by [`Self::to_bytes`].
+    ///
+    /// The result can be applied with [`WindowHandle::set_placement`].
+    pub fn from_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let invalid_data_err = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+        match bytes.first() {
+            Some(&Self::SERIALIZATION_VERSION) => (),
+            Some(_) => return Err(invalid_data_err("Unsupported window placement version")),
+            None => return Err(invalid_data_err("Empty window placement data")),
+        }
+        if bytes.len() != Self::SERIALIZED_LEN_V1 {
+            return Err(invalid_data_err("Invalid window placement data length"));
+        }
+        let mut values = bytes[1..]
+            .chunks_exact(mem::size_of::<u32>())
+            .map(|chunk| u32::from_le_bytes(chunk.try_into().unwrap()));
+        let mut next_value = || values.next().unwrap();
+        let raw_placement = WINDOWPLACEMENT {
+            length: mem::size_of::<WINDOWPLACEMENT>().try_into().unwrap(),
+            flags: WINDOWPLACEMENT_FLAGS(next_value()),
+            showCmd: next_value(),
+            ptMinPosition: Point {
+                x: next_value() as i32,
+                y: next_value() as i32,
+            },
+            ptMaxPosition: Point {
+                x: next_value() as i32,
+                y: next_value() as i32,
+            },
+            rcNormalPosition: Rectangle {
+                left: next_value() as i32,
+                top: next_value() as i32,
+                right: next_value() as i32,
+                bottom: next_value() as i32,
+            },
+        };
+        Ok(Self { raw_placement })
+    }
+}
+
+/// Flags for [`WindowHandle::send_message_timeout`].
+///
+/// Flags can be combined using `|`.
+#[derive(IntoPrimitive, FromPrimitive, Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(u32)]
+pub enum SendTimeoutFlags {
+    /// The calling thread can process other sent messages while waiting.
+    Normal = SMTO_NORMAL.0,
+    /// Prevents the calling thread from processing any other messages while waiting.
+    Block = SMTO_BLOCK.0,
+    /// Returns immediately if the receiving thread appears to be hung.
+    AbortIfHung = SMTO_ABORTIFHUNG.0,
+    /// Waits without a time limit as long as the receiving thread is not hung.
+    NoTimeoutIfNotHung = SMTO_NOTIMEOUTIFNOTHUNG.0,
+    /// Fails if the receiving window is destroyed or its thread exits while waiting.
+    ErrorOnExit = SMTO_ERRORONEXIT.0,
+    #[num_enum(catch_all)]
+    Other(u32),
+}
+
+impl BitOr for SendTimeoutFlags {
+    type Output = SendTimeoutFlags;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self::from(u32::from(self) | u32::from(rhs))
+    }
+}
+
+impl BitOrAssign for SendTimeoutFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = *self | rhs
+    }
+}
+
+impl From<SendTimeoutFlags> for SEND_MESSAGE_TIMEOUT_FLAGS {
+    fn from(value: SendTimeoutFlags) -> Self {
+        SEND_MESSAGE_TIMEOUT_FLAGS(value.into())
+    }
 }
 
 /// Window command corresponding to its buttons in the top right corner.
@@ -885,12 +2400,50 @@ pub struct NotificationIcon<'a, WML> {
 }
 
 impl<'a, WML> NotificationIcon<'a, WML> {
+    /// Returns the area of the icon in screen coordinates.
+    ///
+    /// Fails if the icon is currently not visible, e.g. because it is in the overflow area.
+    pub fn get_rect(&self) -> io::Result<Rectangle> {
+        get_notification_icon_rect(&self.window.handle, self.id)
+    }
+
+    /// Returns whether the icon is currently shown in the visible part of the notification area.
+    ///
+    /// There is no direct API for this, so this is a heuristic based on [`Self::get_rect`]:
+    /// Icons in the overflow area or hidden icons usually have no area on screen. The result may
+    /// therefore be inaccurate, e.g. while the overflow area is opened by the user.
+    pub fn is_in_visible_tray(&self) -> bool {
+        self.get_rect()
+            .is_ok_and(|rect| rect.right > rect.left && rect.bottom > rect.top)
+    }
+
+    /// Sets a window to be shown instead of the standard tooltip while the mouse cursor hovers over the icon.
+    ///
+    /// The window will be positioned next to the icon and hidden again once the cursor leaves it.
+    /// A [`WindowStyle::Popup`] window with the [`WindowExtendedStyle::ToolWindow`] style is usually suitable.
+    ///
+    /// Requires [`NotificationIconVersion::Version4`] and an icon without a shown tooltip,
+    /// e.g. by using [`Self::set_hidden_tooltip_text`],
+    /// see also [`messaging::WindowMessageListener::handle_notification_icon_hover`].
+    pub fn set_rich_popup(&mut self, popup: Option<&'a WindowHandle>) -> io::Result<()> {
+        let key = (self.window.handle.raw_handle.0 as usize, self.id);
+        NOTIFICATION_POPUPS.with(|popups| {
+            let mut popups = popups.borrow_mut();
+            if let Some(popup) = popup {
+                popups.insert(key, popup.raw_handle);
+            } else {
+                popups.remove(&key);
+            }
+        });
+        Ok(())
+    }
+
     /// Sets the icon graphics.
     pub fn set_icon(&mut self, icon: &'a impl Icon) -> io::Result<()> {
         let call_data = get_notification_call_data(
             &self.window.handle,
             self.id,
-            false,
+            None,
             Some(icon.as_handle()?),
             None,
             None,
@@ -909,7 +2462,7 @@ impl<'a, WML> NotificationIcon<'a, WML> {
         let call_data = get_notification_call_data(
             &self.window.handle,
             self.id,
-            false,
+            None,
             None,
             None,
             Some(hidden),
@@ -928,12 +2481,29 @@ impl<'a, WML> NotificationIcon<'a, WML> {
 
     /// Sets the tooltip text when hovering over the icon with the mouse.
     pub fn set_tooltip_text(&mut self, text: &str) -> io::Result<()> {
+        self.set_tooltip(text, true)
+    }
+
+    /// Sets the tooltip text without showing the standard tooltip when hovering over the icon.
+    ///
+    /// The text is still available to accessibility tools. This can be combined with [`Self::set_rich_popup`]
+    /// when using [`NotificationIconVersion::Version4`].
+    pub fn set_hidden_tooltip_text(&mut self, text: &str) -> io::Result<()> {
+        self.set_tooltip(text, false)
+    }
+
+    /// Removes the tooltip text.
+    pub fn clear_tooltip(&mut self) -> io::Result<()> {
+        self.set_tooltip("", false)
+    }
+
+    fn set_tooltip(&mut self, text: &str, show_standard_tooltip: bool) -> io::Result<()> {
         let call_data = get_notification_call_data(
             &self.window.handle,
             self.id,
-            false,
             None,
-            Some(text),
+            None,
+            Some((text, show_standard_tooltip)),
             None,
             None,
         );
@@ -956,7 +2526,7 @@ impl<'a, WML> NotificationIcon<'a, WML> {
         let call_data = get_notification_call_data(
             &self.window.handle,
             self.id,
-            false,
+            None,
             None,
             None,
             None,
@@ -972,12 +2542,80 @@ impl<'a, WML> NotificationIcon<'a, WML> {
         };
         Ok(())
     }
+
+    /// Returns the keyboard focus to the icon in the notification area.
+    ///
+    /// Should be used after finishing an interaction with the icon's UI, e.g. a menu or balloon notification,
+    /// so that keyboard users can continue navigating the notification area.
+    pub fn set_focus(&self) -> io::Result<()> {
+        let call_data =
+            get_notification_call_data(&self.window.handle, self.id, None, None, None, None, None);
+        unsafe {
+            Shell_NotifyIconW(NIM_SETFOCUS, &call_data).if_null_to_error_else_drop(|| {
+                io::Error::new(io::ErrorKind::Other, "Cannot set notification icon focus")
+            })?;
+        };
+        Ok(())
+    }
+
+    /// Applies several changes at once with a single update, avoiding flicker.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use winapi_easy::ui::messaging::EmptyWindowMessageListener;
+    /// use winapi_easy::ui::resource::BuiltinIcon;
+    /// use winapi_easy::ui::{
+    ///     NotificationIconChanges,
+    ///     NotificationIconOptions,
+    ///     Window,
+    ///     WindowClass,
+    ///     WindowClassAppearance,
+    /// };
+    ///
+    /// let listener = EmptyWindowMessageListener;
+    /// let class: WindowClass<EmptyWindowMessageListener> =
+    ///     WindowClass::register_new("myclass", WindowClassAppearance::default(), None)?;
+    /// let window = Window::create_new(&class, &listener, "mywindow")?;
+    /// let mut notification_icon = window.add_notification_icon(NotificationIconOptions {
+    ///     icon: Some(BuiltinIcon::Application),
+    ///     ..Default::default()
+    /// })?;
+    /// let new_icon = BuiltinIcon::Warning;
+    /// notification_icon.modify(NotificationIconChanges {
+    ///     icon: Some(&new_icon),
+    ///     tooltip_text: Some("Warning"),
+    ///     hidden: Some(false),
+    ///     ..Default::default()
+    /// })?;
+    ///
+    /// # Result::<(), std::io::Error>::Ok(())
+    /// ```
+    pub fn modify(&mut self, changes: NotificationIconChanges<'a, '_>) -> io::Result<()> {
+        let call_data = get_notification_call_data(
+            &self.window.handle,
+            self.id,
+            None,
+            changes.icon.map(|icon| icon.as_handle()).transpose()?,
+            changes.tooltip_text.map(|text| (text, true)),
+            changes.hidden,
+            changes.balloon_notification.map(Some),
+        );
+        unsafe {
+            Shell_NotifyIconW(NIM_MODIFY, &call_data).if_null_to_error_else_drop(|| {
+                io::Error::new(io::ErrorKind::Other, "Cannot modify notification icon")
+            })?;
+        };
+        Ok(())
+    }
 }
 
 impl<WML> Drop for NotificationIcon<'_, WML> {
     fn drop(&mut self) {
+        let key = (self.window.handle.raw_handle.0 as usize, self.id);
+        NOTIFICATION_POPUPS.with(|popups| popups.borrow_mut().remove(&key));
         let call_data =
-            get_notification_call_data(&self.window.handle, self.id, false, None, None, None, None);
+            get_notification_call_data(&self.window.handle, self.id, None, None, None, None, None);
         unsafe {
             Shell_NotifyIconW(NIM_DELETE, &call_data)
                 .if_null_to_error_else_drop(|| {
@@ -988,12 +2626,372 @@ impl<WML> Drop for NotificationIcon<'_, WML> {
     }
 }
 
+type TrayAppClickHandler<'a> = Box<dyn FnMut(Point) -> io::Result<()> + 'a>;
+
+/// A notification area application without a visible main window.
+///
+/// Hides the boilerplate of creating a hidden window hosting a [`NotificationIcon`] and a [`PopupMenu`],
+/// as well as running the [`ThreadMessageLoop`].
+///
+/// The menu is shown when right-clicking the icon, if any menu items were added.
+/// Handlers can end [`Self::run`] by calling [`ThreadMessageLoop::post_quit_message`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use winapi_easy::messaging::ThreadMessageLoop;
+/// use winapi_easy::ui::resource::BuiltinIcon;
+/// use winapi_easy::ui::TrayApp;
+///
+/// const EXIT_ID: u32 = 1;
+///
+/// TrayApp::new(BuiltinIcon::default(), "My app")
+///     .on_left_click(|coords| {
+///         println!("Clicked at ({}, {})", coords.x, coords.y);
+///         Ok(())
+///     })
+///     .on_menu_item(EXIT_ID, "Exit", || {
+///         ThreadMessageLoop::post_quit_message();
+///         Ok(())
+///     })
+///     .run()?;
+///
+/// # Result::<(), std::io::Error>::Ok(())
+/// ```
+pub struct TrayApp<'a, I> {
+    icon: I,
+    tooltip_text: String,
+    left_click_handler: Option<TrayAppClickHandler<'a>>,
+    right_click_handler: Option<TrayAppClickHandler<'a>>,
+    menu_items: Vec<TrayAppMenuItem<'a>>,
+}
+
+impl<'a, I: Icon> TrayApp<'a, I> {
+    pub fn new(icon: I, tooltip_text: &str) -> Self {
+        Self {
+            icon,
+            tooltip_text: tooltip_text.to_string(),
+            left_click_handler: None,
+            right_click_handler: None,
+            menu_items: Vec::new(),
+        }
+    }
+
+    /// Sets the handler for left-clicks on the icon, receiving the click coordinates.
+    pub fn on_left_click<F>(mut self, handler: F) -> Self
+    where
+        F: FnMut(Point) -> io::Result<()> + 'a,
+    {
+        self.left_click_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Sets the handler for right-clicks on the icon, receiving the click coordinates.
+    ///
+    /// The handler is called before the menu is shown.
+    pub fn on_right_click<F>(mut self, handler: F) -> Self
+    where
+        F: FnMut(Point) -> io::Result<()> + 'a,
+    {
+        self.right_click_handler = Some(Box::new(handler));
+        self
+    }
+
+    /// Adds a text menu item with the given ID and the handler to call when it is selected.
+    pub fn on_menu_item<F>(mut self, id: u32, text: &str, handler: F) -> Self
+    where
+        F: FnMut() -> io::Result<()> + 'a,
+    {
+        self.menu_items.push(TrayAppMenuItem {
+            id,
+            text: Some(text.to_string()),
+            handler: Some(Box::new(handler)),
+        });
+        self
+    }
+
+    /// Adds a separator line to the menu.
+    pub fn add_menu_separator(mut self) -> Self {
+        self.menu_items.push(TrayAppMenuItem {
+            id: 0,
+            text: None,
+            handler: None,
+        });
+        self
+    }
+
+    /// Shows the icon and runs the thread message loop until a quit message is posted.
+    ///
+    /// Errors returned by handlers end the loop and are returned here.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if a thread message loop is already running on the current thread.
+    pub fn run(mut self) -> io::Result<()> {
+        let listener = TrayAppListener {
+            event: Cell::new(None),
+        };
+        let class: WindowClass<TrayAppListener> = WindowClass::register_new(
+            "winapi_easy_tray_app",
+            WindowClassAppearance::empty(),
+            None,
+        )?;
+        // A hidden top-level window is used instead of a message-only window because the latter
+        // can neither become the foreground window for the menu nor receive the 'TaskbarCreated' broadcast.
+        let window = Window::create_new(&class, &listener, "")?;
+        let window_handle = window.as_ref();
+        let _notification_icon = window.add_notification_icon(NotificationIconOptions {
+            icon_id: Default::default(),
+            icon: Some(self.icon),
+            tooltip_text: Some(self.tooltip_text.as_str()),
+            visible: true,
+            version: Default::default(),
+        })?;
+        let menu = PopupMenu::new()?;
+        for item in &self.menu_items {
+            let menu_item = match &item.text {
+                Some(text) => MenuItem::Text(text),
+                None => MenuItem::Separator,
+            };
+            menu.insert_menu_item(menu_item, item.id, None)?;
+        }
+        let loop_callback = || {
+            match listener.event.take() {
+                Some(TrayAppEvent::LeftClick(coords)) => {
+                    if let Some(handler) = &mut self.left_click_handler {
+                        handler(coords)?;
+                    }
+                }
+                Some(TrayAppEvent::RightClick(coords)) => {
+                    if let Some(handler) = &mut self.right_click_handler {
+                        handler(coords)?;
+                    }
+                    if !self.menu_items.is_empty() {
+                        // Allows the menu to close when clicking elsewhere, it will still be shown on failure
+                        let _ = window_handle.set_as_foreground();
+                        menu.show_popup_menu(window_handle, coords)?;
+                    }
+                }
+                Some(TrayAppEvent::MenuItem(id)) => {
+                    let handler = self
+                        .menu_items
+                        .iter_mut()
+                        .filter(|item| item.id == id)
+                        .find_map(|item| item.handler.as_mut());
+                    if let Some(handler) = handler {
+                        handler()?;
+                    }
+                }
+                None => (),
+            }
+            Ok(())
+        };
+        ThreadMessageLoop::run_thread_message_loop(loop_callback)
+    }
+}
+
+struct TrayAppMenuItem<'a> {
+    id: u32,
+    text: Option<String>,
+    handler: Option<Box<dyn FnMut() -> io::Result<()> + 'a>>,
+}
+
+#[derive(Copy, Clone, Debug)]
+enum TrayAppEvent {
+    LeftClick(Point),
+    RightClick(Point),
+    MenuItem(u32),
+}
+
+struct TrayAppListener {
+    event: Cell<Option<TrayAppEvent>>,
+}
+
+impl WindowMessageListener for TrayAppListener {
+    fn handle_menu_command(&self, _window: &WindowHandle, selected_item_id: u32) {
+        self.event
+            .set(Some(TrayAppEvent::MenuItem(selected_item_id)));
+    }
+
+    fn handle_notification_icon_select(&self, _icon_id: u16, xy_coords: Point) {
+        self.event.set(Some(TrayAppEvent::LeftClick(xy_coords)));
+    }
+
+    fn handle_notification_icon_context_select(&self, _icon_id: u16, xy_coords: Point) {
+        self.event.set(Some(TrayAppEvent::RightClick(xy_coords)));
+    }
+}
+
+fn get_notification_icon_rect(
+    window_handle: &WindowHandle,
+    icon_id: NotificationIconId,
+) -> io::Result<Rectangle> {
+    let mut identifier = NOTIFYICONIDENTIFIER {
+        cbSize: mem::size_of::<NOTIFYICONIDENTIFIER>()
+            .try_into()
+            .expect("NOTIFYICONIDENTIFIER size conversion failed"),
+        hWnd: window_handle.into(),
+        ..Default::default()
+    };
+    match icon_id {
+        NotificationIconId::GUID(id) => identifier.guidItem = id,
+        NotificationIconId::Simple(simple_id) => identifier.uID = simple_id.into(),
+    }
+    let rect = unsafe { Shell_NotifyIconGetRect(&identifier)? };
+    Ok(rect)
+}
+
+fn get_zero_prop_marker_key(key: &str) -> String {
+    format!("{key}_winapi_easy_zero")
+}
+
+fn prop_value_to_bits<T: Copy>(value: T) -> usize {
+    const {
+        assert!(
+            mem::size_of::<T>() <= mem::size_of::<usize>(),
+            "Property values cannot be larger than a pointer"
+        )
+    };
+    let mut bits: usize = 0;
+    unsafe {
+        ptr::copy_nonoverlapping(
+            (&value as *const T).cast::<u8>(),
+            (&mut bits as *mut usize).cast::<u8>(),
+            mem::size_of::<T>(),
+        );
+    }
+    bits
+}
+
+fn prop_value_from_bits<T: Copy>(bits: usize) -> T {
+    const {
+        assert!(
+            mem::size_of::<T>() <= mem::size_of::<usize>(),
+            "Property values cannot be larger than a pointer"
+        )
+    };
+    unsafe { mem::transmute_copy(&bits) }
+}
+
+thread_local! {
+    /// Popups set by [`NotificationIcon::set_rich_popup`], by raw window handle and icon ID.
+    static NOTIFICATION_POPUPS: RefCell<HashMap<(usize, NotificationIconId), HWND>> =
+        RefCell::new(HashMap::new());
+}
+
+#[derive(Copy, Clone)]
+struct NotificationPopup {
+    icon_id: NotificationIconId,
+    popup: HWND,
+}
+
+/// Returns the popups that may belong to the icon with the given message ID.
+///
+/// All GUID icons are reported with the message ID `0`, so all of their popups are candidates in that case.
+fn get_notification_popups(
+    window_handle: &WindowHandle,
+    message_icon_id: u16,
+) -> Vec<NotificationPopup> {
+    let window_key = window_handle.raw_handle.0 as usize;
+    let mut result: Vec<NotificationPopup> = NOTIFICATION_POPUPS.with(|popups| {
+        popups
+            .borrow()
+            .iter()
+            .filter(|((popup_window_key, icon_id), _)| {
+                *popup_window_key == window_key
+                    && match icon_id {
+                        NotificationIconId::Simple(simple_id) => *simple_id == message_icon_id,
+                        NotificationIconId::GUID(_) => message_icon_id == 0,
+                    }
+            })
+            .map(|(&(_, icon_id), &popup)| NotificationPopup { icon_id, popup })
+            .collect()
+    });
+    result.retain(|popup| {
+        WindowHandle::from_maybe_null(popup.popup).is_some_and(|popup| popup.is_window())
+    });
+    result
+}
+
+/// Shows the popup set by [`NotificationIcon::set_rich_popup`], if any.
+pub(crate) fn show_notification_popup(
+    window_handle: &WindowHandle,
+    message_icon_id: u16,
+    anchor: Point,
+) -> io::Result<()> {
+    let anchor_rect = Rectangle {
+        left: anchor.x,
+        top: anchor.y,
+        right: anchor.x,
+        bottom: anchor.y,
+    };
+    let candidates: Vec<(HWND, Rectangle)> =
+        get_notification_popups(window_handle, message_icon_id)
+            .into_iter()
+            .map(|popup| {
+                let icon_rect =
+                    get_notification_icon_rect(window_handle, popup.icon_id).unwrap_or(anchor_rect);
+                (popup.popup, icon_rect)
+            })
+            .collect();
+    // With multiple GUID icons, the one at the anchor is the one being hovered
+    let Some(&(raw_popup, icon_rect)) = candidates
+        .iter()
+        .find(|(_, icon_rect)| {
+            (icon_rect.left..=icon_rect.right).contains(&anchor.x)
+                && (icon_rect.top..=icon_rect.bottom).contains(&anchor.y)
+        })
+        .or(candidates.first())
+    else {
+        return Ok(());
+    };
+    let mut popup_rect: Rectangle = Default::default();
+    unsafe { GetWindowRect(raw_popup, &mut popup_rect)? };
+    let popup_width = popup_rect.right - popup_rect.left;
+    let popup_height = popup_rect.bottom - popup_rect.top;
+    let work_area = desktop::MonitorHandle::from_rect(&icon_rect)
+        .info()?
+        .work_area;
+    // Center above the icon, or below if the taskbar is at the top of the screen
+    let x = ((icon_rect.left + icon_rect.right) / 2 - popup_width / 2)
+        .min(work_area.right - popup_width)
+        .max(work_area.left);
+    let y = if icon_rect.top - popup_height >= work_area.top {
+        icon_rect.top - popup_height
+    } else {
+        icon_rect.bottom
+    }
+    .min(work_area.bottom - popup_height)
+    .max(work_area.top);
+    unsafe {
+        SetWindowPos(
+            raw_popup,
+            HWND_TOPMOST,
+            x,
+            y,
+            0,
+            0,
+            SWP_NOSIZE | SWP_NOACTIVATE | SWP_SHOWWINDOW,
+        )?;
+    }
+    Ok(())
+}
+
+/// Hides the popup set by [`NotificationIcon::set_rich_popup`], if any.
+pub(crate) fn hide_notification_popup(window_handle: &WindowHandle, message_icon_id: u16) {
+    for popup in get_notification_popups(window_handle, message_icon_id) {
+        if let Some(popup) = WindowHandle::from_maybe_null(popup.popup) {
+            let _ = popup.set_show_state(WindowShowState::Hide);
+        }
+    }
+}
+
 fn get_notification_call_data(
     window_handle: &WindowHandle,
     icon_id: NotificationIconId,
-    set_callback_message: bool,
+    callback_version: Option<NotificationIconVersion>,
     maybe_icon: Option<HICON>,
-    maybe_tooltip_str: Option<&str>,
+    maybe_tooltip: Option<(&str, bool)>,
     icon_hidden_state: Option<bool>,
     maybe_balloon_text: Option<Option<BalloonNotification>>,
 ) -> NOTIFYICONDATAW {
@@ -1004,7 +3002,6 @@ fn get_notification_call_data(
         hWnd: window_handle.into(),
         ..Default::default()
     };
-    icon_data.Anonymous.uVersion = NOTIFYICON_VERSION_4;
     match icon_id {
         NotificationIconId::GUID(id) => {
             icon_data.guidItem = id;
@@ -1012,15 +3009,22 @@ fn get_notification_call_data(
         }
         NotificationIconId::Simple(simple_id) => icon_data.uID = simple_id.into(),
     };
-    if set_callback_message {
-        icon_data.uCallbackMessage = messaging::RawMessage::ID_NOTIFICATION_ICON_MSG;
+    if let Some(version) = callback_version {
+        icon_data.Anonymous.uVersion = version.into();
+        // The message layout differs between versions, so the message ID is used to tell them apart
+        icon_data.uCallbackMessage = match version {
+            NotificationIconVersion::Version4 => messaging::RawMessage::ID_NOTIFICATION_ICON_MSG,
+            NotificationIconVersion::Legacy => {
+                messaging::RawMessage::ID_NOTIFICATION_ICON_LEGACY_MSG
+            }
+        };
         icon_data.uFlags |= NIF_MESSAGE;
     }
     if let Some(icon) = maybe_icon {
         icon_data.hIcon = icon;
         icon_data.uFlags |= NIF_ICON;
     }
-    if let Some(tooltip_str) = maybe_tooltip_str {
+    if let Some((tooltip_str, show_standard_tooltip)) = maybe_tooltip {
         let chars = to_wide_chars_iter(tooltip_str)
             .take(icon_data.szTip.len() - 1)
             .chain(std::iter::once(0))
@@ -1029,8 +3033,10 @@ fn get_notification_call_data(
             icon_data.szTip[i] = w_char;
         }
         icon_data.uFlags |= NIF_TIP;
-        // Standard tooltip is normally suppressed on NOTIFYICON_VERSION_4
-        icon_data.uFlags |= NIF_SHOWTIP;
+        if show_standard_tooltip {
+            // Standard tooltip is normally suppressed on NOTIFYICON_VERSION_4
+            icon_data.uFlags |= NIF_SHOWTIP;
+        }
     }
     if let Some(hidden_state) = icon_hidden_state {
         if hidden_state {
@@ -1064,7 +3070,7 @@ fn get_notification_call_data(
 }
 
 /// Notification icon ID given to the Windows API.
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
 pub enum NotificationIconId {
     /// A simple ID.
     Simple(u16),
@@ -1080,6 +3086,20 @@ impl Default for NotificationIconId {
     }
 }
 
+/// Behavior version of a notification icon.
+#[derive(IntoPrimitive, Copy, Clone, Eq, PartialEq, Default, Debug)]
+#[repr(u32)]
+pub enum NotificationIconVersion {
+    /// Enables [`messaging::WindowMessageListener::handle_notification_icon_hover`] for icons without tooltip text,
+    /// allowing custom pop-up UIs instead of the standard tooltip.
+    #[default]
+    Version4 = NOTIFYICON_VERSION_4,
+    /// Behavior of older Windows versions, always using the standard tooltip and sending no hover notifications.
+    ///
+    /// Icons using a [`NotificationIconId::GUID`] will be reported with an ID of `0` to the listener.
+    Legacy = NOTIFYICON_VERSION,
+}
+
 /// Options for a new notification icon used by [`Window::add_notification_icon`].
 #[derive(Eq, PartialEq, Default, Debug)]
 pub struct NotificationIconOptions<I, S> {
@@ -1087,6 +3107,18 @@ pub struct NotificationIconOptions<I, S> {
     pub icon: Option<I>,
     pub tooltip_text: Option<S>,
     pub visible: bool,
+    pub version: NotificationIconVersion,
+}
+
+/// Changes to a notification icon applied together by [`NotificationIcon::modify`].
+///
+/// Properties set to `None` are left unchanged.
+#[derive(Copy, Clone, Default)]
+pub struct NotificationIconChanges<'icon, 'text> {
+    pub icon: Option<&'icon dyn Icon>,
+    pub tooltip_text: Option<&'text str>,
+    pub hidden: Option<bool>,
+    pub balloon_notification: Option<BalloonNotification<'text>>,
 }
 
 /// A Balloon notification above the Windows notification area.
@@ -1137,6 +3169,223 @@ impl From<ProgressState> for TBPFLAG {
     }
 }
 
+/// Confines the mouse cursor to an area of the screen while in scope.
+///
+/// The confinement may be lifted by Windows at any time, e.g. when a different window
+/// is activated, in which case [`Self::reapply`] can be used to restore it.
+#[derive(Debug)]
+pub struct CursorConfinement<'a> {
+    area: Rectangle,
+    window: Option<&'a WindowHandle>,
+}
+
+impl<'a> CursorConfinement<'a> {
+    /// Confines the cursor to a fixed area in screen coordinates.
+    pub fn new(area: Rectangle) -> io::Result<Self> {
+        let result = Self { area, window: None };
+        result.reapply()?;
+        Ok(result)
+    }
+
+    /// Confines the cursor to the current client area of the window.
+    ///
+    /// Use [`Self::follow`] to update the area after the window was moved or resized.
+    pub fn to_window(window: &'a WindowHandle) -> io::Result<Self> {
+        let result = Self {
+            area: window.get_client_area_coords()?,
+            window: Some(window),
+        };
+        result.reapply()?;
+        Ok(result)
+    }
+
+    /// Applies the confinement again, using the same area as before.
+    pub fn reapply(&self) -> io::Result<()> {
+        unsafe { ClipCursor(Some(&self.area))? };
+        Ok(())
+    }
+
+    /// Updates the area to the window's current client area and applies the confinement again.
+    ///
+    /// Same as [`Self::reapply`] if not confined to a window.
+    pub fn follow(&mut self) -> io::Result<()> {
+        if let Some(window) = self.window {
+            self.area = window.get_client_area_coords()?;
+        }
+        self.reapply()
+    }
+
+    /// Returns the area the cursor is currently confined to in screen coordinates.
+    pub fn get_area(&self) -> Rectangle {
+        self.area
+    }
+}
+
+impl Drop for CursorConfinement<'_> {
+    fn drop(&mut self) {
+        let _ = unsafe { ClipCursor(None) };
+    }
+}
+
+/// Watches for changes of the foreground window while in scope.
+///
+/// The callback is called on the thread that created the watcher, which needs to run a message loop
+/// for events to be delivered, e.g. via [`ThreadMessageLoop::run_thread_message_loop`].
+///
+/// [`ThreadMessageLoop::run_thread_message_loop`]: crate::messaging::ThreadMessageLoop::run_thread_message_loop
+pub struct ForegroundWatcher {
+    hook: HWINEVENTHOOK,
+}
+
+impl ForegroundWatcher {
+    thread_local! {
+        static CALLBACKS: RefCell<HashMap<usize, ForegroundCallback>> = RefCell::new(HashMap::new());
+    }
+
+    /// Starts watching, calling the callback with the new foreground window whenever it changes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use winapi_easy::messaging::ThreadMessageLoop;
+    /// use winapi_easy::ui::ForegroundWatcher;
+    ///
+    /// let _watcher = ForegroundWatcher::new(|window| {
+    ///     println!("New foreground window: {}", window.get_caption_text());
+    /// })?;
+    /// ThreadMessageLoop::run_thread_message_loop(|| Ok(()))?;
+    ///
+    /// # Result::<(), std::io::Error>::Ok(())
+    /// ```
+    pub fn new<F>(callback: F) -> io::Result<Self>
+    where
+        F: FnMut(WindowHandle) + 'static,
+    {
+        unsafe extern "system" fn internal_callback(
+            hook: HWINEVENTHOOK,
+            _event: u32,
+            h_wnd: HWND,
+            object_id: i32,
+            _child_id: i32,
+            _event_thread_id: u32,
+            _event_time: u32,
+        ) {
+            if object_id != OBJID_WINDOW.0 {
+                return;
+            }
+            let Some(window) = WindowHandle::from_maybe_null(h_wnd) else {
+                return;
+            };
+            // Cloned out of the map to allow creating or dropping watchers inside of the callback
+            let maybe_callback = ForegroundWatcher::CALLBACKS
+                .with(|callbacks| callbacks.borrow().get(&(hook.0 as usize)).cloned());
+            if let Some(callback) = maybe_callback {
+                catch_unwind_and_abort(|| (callback.borrow_mut())(window));
+            }
+        }
+        let hook = unsafe {
+            SetWinEventHook(
+                EVENT_SYSTEM_FOREGROUND,
+                EVENT_SYSTEM_FOREGROUND,
+                HMODULE::default(),
+                Some(internal_callback),
+                0,
+                0,
+                WINEVENT_OUTOFCONTEXT,
+            )
+        };
+        if hook.is_invalid() {
+            return Err(io::ErrorKind::Other.into());
+        }
+        Self::CALLBACKS.with(|callbacks| {
+            callbacks
+                .borrow_mut()
+                .insert(hook.0 as usize, Rc::new(RefCell::new(callback)));
+        });
+        Ok(Self { hook })
+    }
+}
+
+impl Drop for ForegroundWatcher {
+    fn drop(&mut self) {
+        let _ = unsafe { UnhookWinEvent(self.hook) };
+        Self::CALLBACKS.with(|callbacks| callbacks.borrow_mut().remove(&(self.hook.0 as usize)));
+    }
+}
+
+type ForegroundCallback = Rc<RefCell<dyn FnMut(WindowHandle)>>;
+
+/// Notifications about user session changes, e.g. locking or unlocking the workstation, while in scope.
+///
+/// The changes will be sent to [`messaging::WindowMessageListener::handle_session_change`] of the window.
+#[derive(Debug)]
+pub struct SessionNotifications<'a> {
+    window: &'a WindowHandle,
+}
+
+impl<'a> SessionNotifications<'a> {
+    /// Registers the window for notifications about changes of the current session.
+    pub fn register(window: &'a WindowHandle) -> io::Result<Self> {
+        unsafe { WTSRegisterSessionNotification(window.raw_handle, NOTIFY_FOR_THIS_SESSION)? };
+        Ok(Self { window })
+    }
+}
+
+impl Drop for SessionNotifications<'_> {
+    fn drop(&mut self) {
+        let _ = unsafe { WTSUnRegisterSessionNotification(self.window.raw_handle) };
+    }
+}
+
+/// Notifications about device interface changes for a window while in scope.
+///
+/// The changes will be sent to [`messaging::WindowMessageListener::handle_device_change`] of the window.
+/// This is not required for volume changes, which are always sent to all top-level windows.
+#[derive(Debug)]
+pub struct DeviceNotifications<'a> {
+    handle: HDEVNOTIFY,
+    phantom: PhantomData<&'a WindowHandle>,
+}
+
+impl<'a> DeviceNotifications<'a> {
+    /// Registers the window for notifications about arrival and removal of device interfaces.
+    ///
+    /// Only reports devices of the given device interface class, or all classes if `None`.
+    pub fn register(window: &'a WindowHandle, interface_class: Option<GUID>) -> io::Result<Self> {
+        let filter = DEV_BROADCAST_DEVICEINTERFACE_W {
+            dbcc_size: mem::size_of::<DEV_BROADCAST_DEVICEINTERFACE_W>()
+                .try_into()
+                .unwrap(),
+            dbcc_devicetype: DBT_DEVTYP_DEVICEINTERFACE.0,
+            dbcc_reserved: 0,
+            dbcc_classguid: interface_class.unwrap_or_default(),
+            dbcc_name: [0],
+        };
+        let flags = if interface_class.is_some() {
+            DEVICE_NOTIFY_WINDOW_HANDLE
+        } else {
+            DEVICE_NOTIFY_WINDOW_HANDLE | DEVICE_NOTIFY_ALL_INTERFACE_CLASSES
+        };
+        let handle = unsafe {
+            RegisterDeviceNotificationW(
+                window.raw_handle,
+                &filter as *const DEV_BROADCAST_DEVICEINTERFACE_W as *const c_void,
+                flags,
+            )?
+        };
+        Ok(Self {
+            handle,
+            phantom: PhantomData,
+        })
+    }
+}
+
+impl Drop for DeviceNotifications<'_> {
+    fn drop(&mut self) {
+        let _ = unsafe { UnregisterDeviceNotification(self.handle) };
+    }
+}
+
 /// Taskbar functionality.
 pub struct Taskbar {
     taskbar_list_3: ITaskbarList3,
@@ -1206,6 +3455,77 @@ impl ComInterfaceExt for ITaskbarList3 {
     const CLASS_GUID: GUID = TaskbarList;
 }
 
+/// Association of windows with the virtual desktops of Windows 10 and later.
+///
+/// Virtual desktops are identified by GUIDs, e.g. as returned by [`Self::get_window_desktop_id`].
+/// Enumerating or switching the desktops themselves is not possible with the documented API.
+pub struct VirtualDesktopManager {
+    virtual_desktop_manager: IVirtualDesktopManager,
+}
+
+impl VirtualDesktopManager {
+    pub fn new() -> io::Result<Self> {
+        let result = VirtualDesktopManager {
+            virtual_desktop_manager: IVirtualDesktopManager::new_instance()?,
+        };
+        Ok(result)
+    }
+
+    /// Returns whether the top-level window is on the currently active virtual desktop.
+    pub fn is_window_on_current_desktop(&self, window: &WindowHandle) -> io::Result<bool> {
+        let result = unsafe {
+            self.virtual_desktop_manager
+                .IsWindowOnCurrentVirtualDesktop(HWND::from(window))?
+        };
+        Ok(result.as_bool())
+    }
+
+    /// Returns the ID of the virtual desktop the top-level window is on.
+    pub fn get_window_desktop_id(&self, window: &WindowHandle) -> io::Result<GUID> {
+        let desktop_id = unsafe {
+            self.virtual_desktop_manager
+                .GetWindowDesktopId(HWND::from(window))?
+        };
+        Ok(desktop_id)
+    }
+
+    /// Moves the top-level window to the given virtual desktop.
+    ///
+    /// This is only possible for windows of the current process.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use winapi_easy::ui::{
+    ///     VirtualDesktopManager,
+    ///     WindowHandle,
+    /// };
+    ///
+    /// let manager = VirtualDesktopManager::new()?;
+    /// let foreground_window = WindowHandle::get_foreground_window().unwrap();
+    /// let desktop_id = manager.get_window_desktop_id(&foreground_window)?;
+    /// let my_window = WindowHandle::find(Some("MyWindowClass"), None).unwrap();
+    /// manager.move_window_to_desktop(&my_window, desktop_id)?;
+    ///
+    /// # Result::<(), std::io::Error>::Ok(())
+    /// ```
+    pub fn move_window_to_desktop(
+        &self,
+        window: &WindowHandle,
+        desktop_id: GUID,
+    ) -> io::Result<()> {
+        unsafe {
+            self.virtual_desktop_manager
+                .MoveWindowToDesktop(HWND::from(window), &desktop_id)?
+        };
+        Ok(())
+    }
+}
+
+impl ComInterfaceExt for IVirtualDesktopManager {
+    const CLASS_GUID: GUID = windows::Win32::UI::Shell::VirtualDesktopManager;
+}
+
 /// Creates a console window for the current process if there is none.
 pub fn allocate_console() -> io::Result<()> {
     unsafe {
@@ -1232,11 +3552,133 @@ pub fn lock_workstation() -> io::Result<()> {
     Ok(())
 }
 
+/// DPI awareness of the current process, determining how Windows scales its windows.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum DpiAwarenessContext {
+    /// Windows are bitmap-stretched by the system on high DPI monitors.
+    Unaware,
+    /// Like [`Self::Unaware`], but with improved quality for GDI based content.
+    UnawareGdiScaled,
+    /// Windows use the DPI of the primary monitor at login time and are bitmap-stretched on other monitors.
+    SystemAware,
+    /// Windows are notified of DPI changes and have to scale themselves.
+    PerMonitorAware,
+    /// Like [`Self::PerMonitorAware`], but with automatic scaling of the non-client area and more.
+    PerMonitorAwareV2,
+}
+
+impl DpiAwarenessContext {
+    const ALL: [Self; 5] = [
+        Self::Unaware,
+        Self::UnawareGdiScaled,
+        Self::SystemAware,
+        Self::PerMonitorAware,
+        Self::PerMonitorAwareV2,
+    ];
+}
+
+impl From<DpiAwarenessContext> for DPI_AWARENESS_CONTEXT {
+    fn from(value: DpiAwarenessContext) -> Self {
+        match value {
+            DpiAwarenessContext::Unaware => DPI_AWARENESS_CONTEXT_UNAWARE,
+            DpiAwarenessContext::UnawareGdiScaled => DPI_AWARENESS_CONTEXT_UNAWARE_GDISCALED,
+            DpiAwarenessContext::SystemAware => DPI_AWARENESS_CONTEXT_SYSTEM_AWARE,
+            DpiAwarenessContext::PerMonitorAware => DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE,
+            DpiAwarenessContext::PerMonitorAwareV2 => DPI_AWARENESS_CONTEXT_PER_MONITOR_AWARE_V2,
+        }
+    }
+}
+
+/// Sets the DPI awareness of the current process.
+///
+/// This will fail if the awareness was already set, either by a previous call or the application manifest.
+pub fn set_process_dpi_awareness_context(context: DpiAwarenessContext) -> io::Result<()> {
+    unsafe { SetProcessDpiAwarenessContext(DPI_AWARENESS_CONTEXT::from(context))? };
+    Ok(())
+}
+
+/// Returns the DPI awareness currently in effect.
+///
+/// This is the awareness of the process unless it was changed for the current thread.
+/// Returns `None` if the awareness is not one of the known [`DpiAwarenessContext`] values.
+pub fn get_process_dpi_awareness_context() -> Option<DpiAwarenessContext> {
+    let raw_context = unsafe { GetThreadDpiAwarenessContext() };
+    DpiAwarenessContext::ALL.into_iter().find(|context| unsafe {
+        AreDpiAwarenessContextsEqual(raw_context, DPI_AWARENESS_CONTEXT::from(*context)).as_bool()
+    })
+}
+
+/// Returns `true` if the user chose the dark theme for applications.
+///
+/// Always returns `false` on Windows versions without a dark theme.
+pub fn is_dark_mode() -> io::Result<bool> {
+    let key = match RegistryKey::open(
+        RegistryHive::CurrentUser,
+        r"Software\Microsoft\Windows\CurrentVersion\Themes\Personalize",
+        RegistryAccess::Read,
+    ) {
+        Ok(key) => key,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(false),
+        Err(error) => return Err(error),
+    };
+    match key.get_value("AppsUseLightTheme")? {
+        Some(RegistryValue::Dword(value)) => Ok(value == 0),
+        Some(_) => Err(io::ErrorKind::InvalidData.into()),
+        None => Ok(false),
+    }
+}
+
+/// Returns the accent color used by the system for window frames.
+pub fn get_accent_color() -> io::Result<Rgb> {
+    let mut raw_color: u32 = 0;
+    let mut opaque_blend = BOOL::default();
+    unsafe { DwmGetColorizationColor(&mut raw_color, &mut opaque_blend)? };
+    // Format is 0xAARRGGBB
+    let [blue, green, red, _alpha] = raw_color.to_le_bytes();
+    Ok(Rgb::new(red, green, blue))
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use more_asserts::*;
+    use windows::Win32::UI::WindowsAndMessaging::WM_NULL;
 
     use super::*;
+    use crate::ui::messaging::EmptyWindowMessageListener;
+
+    /// Calls the closure with a new window of a new class, for tests that need any window to work with.
+    pub(crate) fn with_test_window<F>(f: F) -> io::Result<()>
+    where
+        F: FnOnce(&Window<EmptyWindowMessageListener>) -> io::Result<()>,
+    {
+        let listener = EmptyWindowMessageListener;
+        let class: WindowClass<EmptyWindowMessageListener> =
+            WindowClass::register_new("testclass", WindowClassAppearance::empty(), None)?;
+        let window = Window::create_new(&class, &listener, "")?;
+        f(&window)
+    }
+
+    #[test]
+    fn window_placement_bytes_roundtrip() -> io::Result<()> {
+        let window = WindowHandle::get_desktop_window()?;
+        let mut placement = window.get_placement()?;
+        placement.set_restored_position(Rectangle {
+            left: -10,
+            top: 20,
+            right: 300,
+            bottom: 400,
+        });
+        let bytes = placement.to_bytes();
+        let restored = WindowPlacement::from_bytes(&bytes)?;
+        assert_eq!(restored.to_bytes(), bytes);
+        assert_eq!(
+            restored.get_restored_position(),
+            placement.get_restored_position()
+        );
+        assert!(WindowPlacement::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        assert!(WindowPlacement::from_bytes(&[]).is_err());
+        Ok(())
+    }
 
     #[test]
     fn check_toplevel_windows() -> io::Result<()> {
@@ -1253,6 +3695,194 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn get_snap_region_area() {
+        let work_area = Rectangle {
+            left: 100,
+            top: 0,
+            right: 1100,
+            bottom: 800,
+        };
+        assert_eq!(
+            SnapRegion::LeftHalf.get_area(work_area),
+            Rectangle {
+                left: 100,
+                top: 0,
+                right: 600,
+                bottom: 800,
+            }
+        );
+        assert_eq!(
+            SnapRegion::BottomRightQuarter.get_area(work_area),
+            Rectangle {
+                left: 600,
+                top: 400,
+                right: 1100,
+                bottom: 800,
+            }
+        );
+        assert_eq!(SnapRegion::Maximize.get_area(work_area), work_area);
+    }
+
+    #[test]
+    fn get_move_mode_area() {
+        let source_work_area = Rectangle {
+            left: 0,
+            top: 0,
+            right: 1000,
+            bottom: 800,
+        };
+        let target_work_area = Rectangle {
+            left: 1000,
+            top: 0,
+            right: 3000,
+            bottom: 1600,
+        };
+        let window_rect = Rectangle {
+            left: 100,
+            top: 200,
+            right: 600,
+            bottom: 600,
+        };
+        assert_eq!(
+            MoveMode::PreserveRelative.get_area(window_rect, source_work_area, target_work_area),
+            Rectangle {
+                left: 1200,
+                top: 400,
+                right: 2200,
+                bottom: 1200,
+            }
+        );
+        assert_eq!(
+            MoveMode::Center.get_area(window_rect, source_work_area, target_work_area),
+            Rectangle {
+                left: 1750,
+                top: 600,
+                right: 2250,
+                bottom: 1000,
+            }
+        );
+    }
+
+    #[test]
+    fn walk_zorder() {
+        let top_window = WindowHandle::get_top_window().unwrap();
+        assert!(top_window.get_prev_in_zorder().is_none());
+        if let Some(next_window) = top_window.get_next_in_zorder() {
+            assert_eq!(next_window.get_prev_in_zorder(), Some(top_window));
+        }
+    }
+
+    #[cfg(feature = "process")]
+    #[test]
+    fn check_process_windows() -> io::Result<()> {
+        with_test_window(|window| {
+            let process_windows =
+                WindowHandle::get_process_windows(crate::process::Process::current().get_id())?;
+            assert!(process_windows.contains(window.as_ref()));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn scale_for_dpi() {
+        let point = Point { x: 10, y: -3 };
+        assert_eq!(point.scale_for_dpi(96, 144), Point { x: 15, y: -5 });
+        assert_eq!(point.scale_for_dpi(96, 96), point);
+        let rect = Rectangle {
+            left: -100,
+            top: 0,
+            right: 101,
+            bottom: i32::MAX,
+        };
+        assert_eq!(
+            rect.scale_for_dpi(144, 96),
+            Rectangle {
+                left: -67,
+                top: 0,
+                right: 67,
+                bottom: 1431655765,
+            }
+        );
+        assert_eq!(rect.scale_for_dpi(96, 192).bottom, i32::MAX);
+    }
+
+    #[test]
+    fn check_dpi_awareness() {
+        assert!(get_process_dpi_awareness_context().is_some());
+    }
+
+    #[test]
+    fn check_theme() -> io::Result<()> {
+        std::hint::black_box(is_dark_mode()?);
+        std::hint::black_box(get_accent_color()?);
+        Ok(())
+    }
+
+    #[test]
+    fn add_and_remove_foreground_watchers() -> io::Result<()> {
+        let watcher1 = ForegroundWatcher::new(|_window| ())?;
+        let watcher2 = ForegroundWatcher::new(|_window| ())?;
+        drop(watcher1);
+        drop(watcher2);
+        Ok(())
+    }
+
+    #[test]
+    fn new_message_only_window() -> io::Result<()> {
+        let listener = EmptyWindowMessageListener;
+        let class: WindowClass<EmptyWindowMessageListener> =
+            WindowClass::register_new("myclass2", WindowClassAppearance::empty(), None)?;
+        let window = Window::create_new_message_only(&class, &listener)?;
+        assert!(window.as_ref().is_window());
+        assert!(!window.as_ref().is_visible());
+        Ok(())
+    }
+
+    #[test]
+    fn set_get_remove_prop() -> io::Result<()> {
+        const KEY: &str = "winapi_easy_test_prop";
+        let listener = EmptyWindowMessageListener;
+        let class: WindowClass<EmptyWindowMessageListener> =
+            WindowClass::register_new("myclass3", WindowClassAppearance::empty(), None)?;
+        let window = Window::create_new_message_only(&class, &listener)?;
+        let window_handle = window.as_ref();
+        assert_eq!(window_handle.get_prop::<u32>(KEY), None);
+        window_handle.set_prop(KEY, 42_u32)?;
+        assert_eq!(window_handle.get_prop::<u32>(KEY), Some(42));
+        window_handle.set_prop(KEY, 0_u32)?;
+        assert_eq!(window_handle.get_prop::<u32>(KEY), Some(0));
+        assert_eq!(window_handle.remove_prop::<u32>(KEY), Some(0));
+        assert_eq!(window_handle.get_prop::<u32>(KEY), None);
+        Ok(())
+    }
+
+    #[test]
+    fn change_style_and_redraw_frame() -> io::Result<()> {
+        with_test_window(|window| {
+            let window_handle = window.as_ref();
+            window_handle.set_style(WindowStyle::Popup)?;
+            window_handle.redraw_frame()?;
+            assert_eq!(window_handle.get_style(), WindowStyle::Popup);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn adopt_owned_window() -> io::Result<()> {
+        let listener = EmptyWindowMessageListener;
+        let class: WindowClass<EmptyWindowMessageListener> =
+            WindowClass::register_new("myclass4", WindowClassAppearance::empty(), None)?;
+        let window = Window::create_new_message_only(&class, &listener)?;
+        let raw_handle = HWND::from(window.as_ref());
+        mem::forget(window);
+        let handle = WindowHandle::from_non_null(raw_handle);
+        let adopted_window = unsafe { Window::from_handle_owned(handle, &class, &listener) }?;
+        drop(adopted_window);
+        assert!(!WindowHandle::from_non_null(raw_handle).is_window());
+        Ok(())
+    }
+
     #[test]
     fn new_window_with_class() -> io::Result<()> {
         struct MyListener;
@@ -1269,6 +3899,7 @@ mod tests {
                 icon: Some(icon),
                 ..Default::default()
             },
+            None,
         )?;
         let window = Window::create_new(&class, &listener, WINDOW_NAME)?;
         let notification_icon_options = NotificationIconOptions {
@@ -1291,4 +3922,254 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn new_window_with_class_style() -> io::Result<()> {
+        let listener = EmptyWindowMessageListener;
+        let class: WindowClass<EmptyWindowMessageListener> = WindowClass::register_new(
+            "myclass7",
+            WindowClassAppearance::empty(),
+            Some(WindowClassStyle::DoubleClicks | WindowClassStyle::DropShadow),
+        )?;
+        let window = Window::create_new(&class, &listener, "")?;
+        assert!(window.as_ref().is_window());
+        Ok(())
+    }
+
+    #[test]
+    fn new_window_is_not_cloaked() -> io::Result<()> {
+        with_test_window(|window| {
+            assert!(!window.as_ref().is_cloaked()?);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn check_alt_tab_window() -> io::Result<()> {
+        with_test_window(|window| {
+            let window_handle = window.as_ref();
+            assert!(!window_handle.is_alt_tab_window()?);
+            window_handle.set_show_state(WindowShowState::ShowNoActivate)?;
+            assert!(window_handle.is_alt_tab_window()?);
+            window_handle.set_tool_window(true)?;
+            assert!(!window_handle.is_alt_tab_window()?);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn set_tool_window() -> io::Result<()> {
+        with_test_window(|window| {
+            let has_tool_window_style = |window: &WindowHandle| {
+                u32::from(window.get_extended_style()) & u32::from(WindowExtendedStyle::ToolWindow)
+                    != 0
+            };
+            window.as_ref().set_tool_window(true)?;
+            assert!(has_tool_window_style(window.as_ref()));
+            window.as_ref().set_tool_window(false)?;
+            assert!(!has_tool_window_style(window.as_ref()));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn set_accept_files() -> io::Result<()> {
+        with_test_window(|window| {
+            window.as_ref().set_accept_files(true);
+            assert_ne!(
+                u32::from(window.as_ref().get_extended_style())
+                    & u32::from(WindowExtendedStyle::AcceptFiles),
+                0
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn get_client_area_coords() -> io::Result<()> {
+        with_test_window(|window| {
+            let client_area = window.as_ref().get_client_area_coords()?;
+            assert_le!(client_area.left, client_area.right);
+            assert_le!(client_area.top, client_area.bottom);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn draw_on_window_dc() -> io::Result<()> {
+        with_test_window(|window| {
+            let window_dc = window.as_ref().get_dc()?;
+            window_dc.draw_line(
+                Point { x: 0, y: 0 },
+                Point { x: 10, y: 10 },
+                Default::default(),
+            )?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn set_opacity() -> io::Result<()> {
+        with_test_window(|window| {
+            window.as_ref().set_opacity(128)?;
+            assert_ne!(
+                u32::from(window.as_ref().get_extended_style())
+                    & u32::from(WindowExtendedStyle::Layered),
+                0
+            );
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn wait_for_window() -> io::Result<()> {
+        with_test_window(|window| {
+            window
+                .as_ref()
+                .set_caption_text("winapi_easy_wait_for_window_test")?;
+            let class_name = window.as_ref().get_class_name()?;
+            let matcher = WindowMatcher {
+                class_name: Some(&class_name),
+                caption_contains: Some("wait_for_window"),
+            };
+            let found_window = WindowHandle::wait_for(matcher, Some(Duration::ZERO))?;
+            assert_eq!(found_window.as_ref(), Some(window.as_ref()));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn find_window() -> io::Result<()> {
+        const CAPTION_TEXT: &str = "winapi_easy_find_window_test";
+        with_test_window(|window| {
+            window.as_ref().set_caption_text(CAPTION_TEXT)?;
+            let class_name = window.as_ref().get_class_name()?;
+            let found_window = WindowHandle::find(Some(&class_name), Some(CAPTION_TEXT));
+            assert_eq!(found_window.as_ref(), Some(window.as_ref()));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn get_text_via_message() -> io::Result<()> {
+        const CAPTION_TEXT: &str = "Testwindow";
+        with_test_window(|window| {
+            window.as_ref().set_caption_text(CAPTION_TEXT)?;
+            assert_eq!(window.as_ref().get_text_via_message()?, CAPTION_TEXT);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn set_text_via_message() -> io::Result<()> {
+        const CAPTION_TEXT: &str = "Testwindow";
+        with_test_window(|window| {
+            window.as_ref().set_text_via_message(CAPTION_TEXT)?;
+            assert_eq!(window.as_ref().get_caption_text(), CAPTION_TEXT);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn register_session_notifications() -> io::Result<()> {
+        with_test_window(|window| {
+            let session_notifications = SessionNotifications::register(window.as_ref())?;
+            drop(session_notifications);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn register_device_notifications() -> io::Result<()> {
+        with_test_window(|window| {
+            let device_notifications = DeviceNotifications::register(window.as_ref(), None)?;
+            drop(device_notifications);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn get_app_icon() -> io::Result<()> {
+        with_test_window(|window| {
+            let app_icon = window.as_ref().get_app_icon()?;
+            assert!(!app_icon.as_handle()?.is_invalid());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn get_window_info() -> io::Result<()> {
+        with_test_window(|window| {
+            let window_handle = window.as_ref();
+            let info = window_handle.get_info()?;
+            assert_eq!(info.style, window_handle.get_style());
+            assert_eq!(info.extended_style, window_handle.get_extended_style());
+            assert_le!(info.window_area.left, info.client_area.left);
+            assert_le!(info.window_area.top, info.client_area.top);
+            assert_ge!(info.window_area.right, info.client_area.right);
+            assert_ge!(info.window_area.bottom, info.client_area.bottom);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn set_hidden_and_clear_tooltip() -> io::Result<()> {
+        with_test_window(|window| {
+            let mut notification_icon = window.add_notification_icon(NotificationIconOptions {
+                icon: Some(BuiltinIcon::default()),
+                visible: false,
+                ..Default::default()
+            })?;
+            notification_icon.set_hidden_tooltip_text("A hidden tooltip!")?;
+            notification_icon.clear_tooltip()?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn modify_notification_icon() -> io::Result<()> {
+        with_test_window(|window| {
+            let icon = BuiltinIcon::default();
+            let mut notification_icon = window.add_notification_icon(NotificationIconOptions {
+                icon: Some(icon),
+                visible: false,
+                ..Default::default()
+            })?;
+            notification_icon.modify(NotificationIconChanges {
+                icon: Some(&icon),
+                tooltip_text: Some("A modified tooltip!"),
+                hidden: Some(true),
+                ..Default::default()
+            })?;
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn hidden_notification_icon_not_in_visible_tray() -> io::Result<()> {
+        with_test_window(|window| {
+            let notification_icon = window.add_notification_icon(NotificationIconOptions {
+                icon: Some(BuiltinIcon::default()),
+                visible: false,
+                ..Default::default()
+            })?;
+            assert!(!notification_icon.is_in_visible_tray());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn send_null_message_with_timeout() -> io::Result<()> {
+        with_test_window(|window| {
+            let result = window.as_ref().send_message_timeout(
+                WM_NULL,
+                0,
+                0,
+                Duration::from_secs(1),
+                SendTimeoutFlags::AbortIfHung,
+            )?;
+            assert_eq!(result, Some(0));
+            Ok(())
+        })
+    }
 }
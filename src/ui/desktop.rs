@@ -0,0 +1,524 @@
+//! Desktop, monitors and screen metrics.
+
+use std::ffi::c_void;
+use std::io;
+use std::mem;
+use std::time::Duration;
+
+use num_enum::IntoPrimitive;
+use windows::Win32::Devices::Display::{
+    DestroyPhysicalMonitors,
+    DisplayConfigGetDeviceInfo,
+    GetDisplayConfigBufferSizes,
+    GetMonitorBrightness,
+    GetNumberOfPhysicalMonitorsFromHMONITOR,
+    GetPhysicalMonitorsFromHMONITOR,
+    QueryDisplayConfig,
+    SetMonitorBrightness,
+    DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+    DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME,
+    DISPLAYCONFIG_DEVICE_INFO_HEADER,
+    DISPLAYCONFIG_MODE_INFO,
+    DISPLAYCONFIG_PATH_INFO,
+    DISPLAYCONFIG_SOURCE_DEVICE_NAME,
+    DISPLAYCONFIG_TARGET_DEVICE_NAME,
+    PHYSICAL_MONITOR,
+    QDC_ONLY_ACTIVE_PATHS,
+};
+use windows::Win32::Foundation::{
+    BOOL,
+    ERROR_INSUFFICIENT_BUFFER,
+    HWND,
+    LPARAM,
+    POINT,
+    RECT,
+    WIN32_ERROR,
+};
+use windows::Win32::Graphics::Gdi::{
+    EnumDisplayMonitors,
+    GetMonitorInfoW,
+    MonitorFromPoint,
+    MonitorFromRect,
+    MonitorFromWindow,
+    HDC,
+    HMONITOR,
+    MONITORINFO,
+    MONITORINFOEXW,
+    MONITOR_DEFAULTTONEAREST,
+    MONITOR_DEFAULTTOPRIMARY,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    GetSystemMetrics,
+    SystemParametersInfoW,
+    MONITORINFOF_PRIMARY,
+    SM_CMONITORS,
+    SM_CXBORDER,
+    SM_CXDOUBLECLK,
+    SM_CXDRAG,
+    SM_CXICON,
+    SM_CXSCREEN,
+    SM_CXSIZEFRAME,
+    SM_CXSMICON,
+    SM_CXVIRTUALSCREEN,
+    SM_CXVSCROLL,
+    SM_CYBORDER,
+    SM_CYCAPTION,
+    SM_CYDOUBLECLK,
+    SM_CYDRAG,
+    SM_CYHSCROLL,
+    SM_CYICON,
+    SM_CYMENU,
+    SM_CYSCREEN,
+    SM_CYSIZEFRAME,
+    SM_CYSMICON,
+    SM_CYVIRTUALSCREEN,
+    SM_XVIRTUALSCREEN,
+    SM_YVIRTUALSCREEN,
+    SPIF_SENDCHANGE,
+    SPIF_UPDATEINIFILE,
+    SPI_GETFOREGROUNDLOCKTIMEOUT,
+    SPI_GETWORKAREA,
+    SPI_SETFOREGROUNDLOCKTIMEOUT,
+    SYSTEM_METRICS_INDEX,
+    SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS,
+};
+
+use crate::internal::{
+    catch_unwind_and_abort,
+    CustomAutoDrop,
+    ReturnValue,
+};
+use crate::string::ZeroTerminatedWideString;
+use crate::ui::{
+    Rectangle,
+    WindowHandle,
+};
+
+/// A system metric as returned by [`get_system_metric`].
+///
+/// All dimensions are in pixels and scaled for the DPI awareness of the calling thread.
+#[derive(IntoPrimitive, Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(i32)]
+pub enum SystemMetric {
+    BorderWidth = SM_CXBORDER.0,
+    BorderHeight = SM_CYBORDER.0,
+    /// Width of the sizing border around a resizable window.
+    ResizeFrameWidth = SM_CXSIZEFRAME.0,
+    /// Height of the sizing border around a resizable window.
+    ResizeFrameHeight = SM_CYSIZEFRAME.0,
+    CaptionHeight = SM_CYCAPTION.0,
+    MenuBarHeight = SM_CYMENU.0,
+    VerticalScrollBarWidth = SM_CXVSCROLL.0,
+    HorizontalScrollBarHeight = SM_CYHSCROLL.0,
+    /// Width of the area in which a second click counts as a double-click.
+    DoubleClickWidth = SM_CXDOUBLECLK.0,
+    /// Height of the area in which a second click counts as a double-click.
+    DoubleClickHeight = SM_CYDOUBLECLK.0,
+    /// Distance the mouse has to move horizontally with a pressed button before a drag operation starts.
+    DragWidth = SM_CXDRAG.0,
+    /// Distance the mouse has to move vertically with a pressed button before a drag operation starts.
+    DragHeight = SM_CYDRAG.0,
+    IconWidth = SM_CXICON.0,
+    IconHeight = SM_CYICON.0,
+    SmallIconWidth = SM_CXSMICON.0,
+    SmallIconHeight = SM_CYSMICON.0,
+    /// Number of monitors that are part of the desktop.
+    MonitorCount = SM_CMONITORS.0,
+}
+
+/// Returns the value of a system metric.
+pub fn get_system_metric(metric: SystemMetric) -> i32 {
+    unsafe { GetSystemMetrics(SYSTEM_METRICS_INDEX(metric.into())) }
+}
+
+/// Returns the area of the primary monitor in screen coordinates.
+pub fn get_primary_screen_rect() -> Rectangle {
+    let (width, height) = unsafe { (GetSystemMetrics(SM_CXSCREEN), GetSystemMetrics(SM_CYSCREEN)) };
+    Rectangle {
+        left: 0,
+        top: 0,
+        right: width,
+        bottom: height,
+    }
+}
+
+/// Returns the bounding rectangle of all monitors in screen coordinates.
+pub fn get_virtual_screen_rect() -> Rectangle {
+    let (left, top, width, height) = unsafe {
+        (
+            GetSystemMetrics(SM_XVIRTUALSCREEN),
+            GetSystemMetrics(SM_YVIRTUALSCREEN),
+            GetSystemMetrics(SM_CXVIRTUALSCREEN),
+            GetSystemMetrics(SM_CYVIRTUALSCREEN),
+        )
+    };
+    Rectangle {
+        left,
+        top,
+        right: left + width,
+        bottom: top + height,
+    }
+}
+
+/// Returns the area of the primary monitor not covered by the taskbar and other app bars, in screen coordinates.
+pub fn get_work_area() -> io::Result<Rectangle> {
+    let mut work_area = Rectangle::default();
+    unsafe {
+        SystemParametersInfoW(
+            SPI_GETWORKAREA,
+            0,
+            Some(&mut work_area as *mut Rectangle as *mut c_void),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )?;
+    }
+    Ok(work_area)
+}
+
+/// Returns the time after user input during which other applications are not allowed
+/// to bring their windows to the foreground.
+///
+/// See also [`WindowHandle::set_as_foreground`].
+pub fn get_foreground_lock_timeout() -> io::Result<Duration> {
+    let mut timeout_millis: u32 = 0;
+    unsafe {
+        SystemParametersInfoW(
+            SPI_GETFOREGROUNDLOCKTIMEOUT,
+            0,
+            Some(&mut timeout_millis as *mut u32 as *mut c_void),
+            SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0),
+        )?;
+    }
+    Ok(Duration::from_millis(timeout_millis.into()))
+}
+
+/// Sets the foreground lock timeout, see [`get_foreground_lock_timeout`].
+///
+/// A timeout of zero allows [`WindowHandle::set_as_foreground`] to succeed at any time.
+///
+/// This is a global setting affecting all applications. If `persist` is `false`, the change only lasts
+/// until the user logs off. Only processes that are currently allowed to set the foreground window
+/// can change this setting.
+pub fn set_foreground_lock_timeout(timeout: Duration, persist: bool) -> io::Result<()> {
+    let timeout_millis: u32 = timeout.as_millis().try_into().unwrap_or(u32::MAX);
+    let flags = if persist {
+        SPIF_UPDATEINIFILE | SPIF_SENDCHANGE
+    } else {
+        SYSTEM_PARAMETERS_INFO_UPDATE_FLAGS(0)
+    };
+    unsafe {
+        // The value is passed directly as the pointer parameter
+        SystemParametersInfoW(
+            SPI_SETFOREGROUNDLOCKTIMEOUT,
+            0,
+            Some(timeout_millis as usize as *mut c_void),
+            flags,
+        )?;
+    }
+    Ok(())
+}
+
+/// A handle to a display monitor.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct MonitorHandle {
+    raw_handle: HMONITOR,
+}
+
+impl MonitorHandle {
+    /// Returns all monitors that are part of the desktop.
+    pub fn get_all() -> io::Result<Vec<Self>> {
+        unsafe extern "system" fn callback(
+            handle: HMONITOR,
+            _hdc: HDC,
+            _area: *mut RECT,
+            app_value: LPARAM,
+        ) -> BOOL {
+            let call = || {
+                let result = &mut *(app_value.0 as *mut Vec<MonitorHandle>);
+                result.push(MonitorHandle { raw_handle: handle });
+                true.into()
+            };
+            catch_unwind_and_abort(call)
+        }
+        let mut result: Vec<Self> = Vec::new();
+        unsafe {
+            EnumDisplayMonitors(
+                HDC::default(),
+                None,
+                Some(callback),
+                LPARAM(&mut result as *mut Vec<Self> as isize),
+            )
+            .if_null_to_error_else_drop(|| io::ErrorKind::Other.into())?;
+        }
+        Ok(result)
+    }
+
+    /// Returns the primary monitor.
+    pub fn get_primary() -> Self {
+        let raw_handle = unsafe { MonitorFromPoint(POINT::default(), MONITOR_DEFAULTTOPRIMARY) };
+        Self { raw_handle }
+    }
+
+    /// Returns the monitor that has the largest area of intersection with the window,
+    /// or the nearest monitor if there is no intersection.
+    pub fn from_window(window: &WindowHandle) -> Self {
+        let raw_handle = unsafe { MonitorFromWindow(HWND::from(window), MONITOR_DEFAULTTONEAREST) };
+        Self { raw_handle }
+    }
+
+    /// Returns the monitor that has the largest area of intersection with the rectangle,
+    /// or the nearest monitor if there is no intersection.
+    pub fn from_rect(rect: &Rectangle) -> Self {
+        let raw_handle = unsafe { MonitorFromRect(rect, MONITOR_DEFAULTTONEAREST) };
+        Self { raw_handle }
+    }
+
+    pub fn info(&self) -> io::Result<MonitorInfo> {
+        let raw_info = self.get_raw_info()?;
+        Ok(MonitorInfo {
+            monitor_area: raw_info.monitorInfo.rcMonitor,
+            work_area: raw_info.monitorInfo.rcWork,
+            is_primary: raw_info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0,
+            device_name: ZeroTerminatedWideString(raw_info.szDevice.to_vec())
+                .to_os_string()
+                .to_string_lossy()
+                .into_owned(),
+        })
+    }
+
+    /// Returns the human-readable name of the monitor, e.g. the model name.
+    ///
+    /// The name may be empty for some monitors, e.g. internal laptop displays or monitors without EDID data.
+    pub fn get_friendly_name(&self) -> io::Result<String> {
+        let gdi_device_name = self.get_raw_info()?.szDevice;
+        let paths = get_active_display_config_paths()?;
+        for path in paths {
+            let mut source_name = DISPLAYCONFIG_SOURCE_DEVICE_NAME {
+                header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+                    r#type: DISPLAYCONFIG_DEVICE_INFO_GET_SOURCE_NAME,
+                    size: mem::size_of::<DISPLAYCONFIG_SOURCE_DEVICE_NAME>()
+                        .try_into()
+                        .unwrap(),
+                    adapterId: path.sourceInfo.adapterId,
+                    id: path.sourceInfo.id,
+                },
+                ..Default::default()
+            };
+            display_config_get_device_info(&mut source_name.header)?;
+            if source_name.viewGdiDeviceName != gdi_device_name {
+                continue;
+            }
+            let mut target_name = DISPLAYCONFIG_TARGET_DEVICE_NAME {
+                header: DISPLAYCONFIG_DEVICE_INFO_HEADER {
+                    r#type: DISPLAYCONFIG_DEVICE_INFO_GET_TARGET_NAME,
+                    size: mem::size_of::<DISPLAYCONFIG_TARGET_DEVICE_NAME>()
+                        .try_into()
+                        .unwrap(),
+                    adapterId: path.targetInfo.adapterId,
+                    id: path.targetInfo.id,
+                },
+                ..Default::default()
+            };
+            display_config_get_device_info(&mut target_name.header)?;
+            return Ok(
+                ZeroTerminatedWideString(target_name.monitorFriendlyDeviceName.to_vec())
+                    .to_os_string()
+                    .to_string_lossy()
+                    .into_owned(),
+            );
+        }
+        Err(io::ErrorKind::NotFound.into())
+    }
+
+    /// Returns the backlight brightness of the monitor in percent.
+    ///
+    /// Requires a monitor supporting DDC/CI, which is usually not the case for internal laptop displays.
+    /// Fails with [`io::ErrorKind::Unsupported`] for other monitors.
+    pub fn get_brightness(&self) -> io::Result<u8> {
+        let physical_monitors = self.get_physical_monitors()?;
+        let physical_monitor = physical_monitors
+            .value
+            .first()
+            .ok_or(io::ErrorKind::NotFound)?;
+        let (minimum, current, maximum) = get_physical_monitor_brightness(physical_monitor)?;
+        if maximum <= minimum {
+            return Ok(100);
+        }
+        let percent = (current.clamp(minimum, maximum) - minimum) * 100 / (maximum - minimum);
+        Ok(percent.try_into().unwrap())
+    }
+
+    /// Sets the backlight brightness of the monitor in percent, with values above 100 being treated as 100.
+    ///
+    /// Has the same requirements as [`Self::get_brightness`].
+    pub fn set_brightness(&self, percent: u8) -> io::Result<()> {
+        let percent = u32::from(percent.min(100));
+        let physical_monitors = self.get_physical_monitors()?;
+        // There may be multiple physical monitors when duplicating the display
+        for physical_monitor in &physical_monitors.value {
+            let (minimum, _, maximum) = get_physical_monitor_brightness(physical_monitor)?;
+            let new_brightness = minimum + maximum.saturating_sub(minimum) * percent / 100;
+            unsafe { SetMonitorBrightness(physical_monitor.hPhysicalMonitor, new_brightness) }
+                .if_null_to_error_else_drop(brightness_unsupported_error)?;
+        }
+        Ok(())
+    }
+
+    fn get_physical_monitors(&self) -> io::Result<CustomAutoDrop<Vec<PHYSICAL_MONITOR>>> {
+        let mut num_monitors: u32 = 0;
+        unsafe { GetNumberOfPhysicalMonitorsFromHMONITOR(self.raw_handle, &mut num_monitors)? };
+        let mut physical_monitors: Vec<PHYSICAL_MONITOR> =
+            vec![Default::default(); num_monitors as usize];
+        unsafe { GetPhysicalMonitorsFromHMONITOR(self.raw_handle, &mut physical_monitors)? };
+        Ok(CustomAutoDrop {
+            value: physical_monitors,
+            drop_fn: |physical_monitors| {
+                let _ = unsafe { DestroyPhysicalMonitors(physical_monitors) };
+            },
+        })
+    }
+
+    fn get_raw_info(&self) -> io::Result<MONITORINFOEXW> {
+        let mut raw_info = MONITORINFOEXW {
+            monitorInfo: MONITORINFO {
+                cbSize: mem::size_of::<MONITORINFOEXW>().try_into().unwrap(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        unsafe {
+            GetMonitorInfoW(self.raw_handle, &mut raw_info.monitorInfo)
+                .if_null_to_error_else_drop(|| io::ErrorKind::Other.into())?;
+        }
+        Ok(raw_info)
+    }
+}
+
+impl From<MonitorHandle> for HMONITOR {
+    /// Returns the underlying raw monitor handle used by [`windows`].
+    fn from(value: MonitorHandle) -> Self {
+        value.raw_handle
+    }
+}
+
+/// Information about a monitor.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct MonitorInfo {
+    /// Area of the monitor in screen coordinates.
+    pub monitor_area: Rectangle,
+    /// Area of the monitor not covered by the taskbar and other app bars, in screen coordinates.
+    pub work_area: Rectangle,
+    pub is_primary: bool,
+    /// GDI device name, e.g. `\\.\DISPLAY1`.
+    pub device_name: String,
+}
+
+/// Returns the minimum, current and maximum brightness.
+fn get_physical_monitor_brightness(
+    physical_monitor: &PHYSICAL_MONITOR,
+) -> io::Result<(u32, u32, u32)> {
+    let mut minimum: u32 = 0;
+    let mut current: u32 = 0;
+    let mut maximum: u32 = 0;
+    unsafe {
+        GetMonitorBrightness(
+            physical_monitor.hPhysicalMonitor,
+            &mut minimum,
+            &mut current,
+            &mut maximum,
+        )
+    }
+    .if_null_to_error_else_drop(brightness_unsupported_error)?;
+    Ok((minimum, current, maximum))
+}
+
+fn brightness_unsupported_error() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        format!(
+            "Monitor brightness cannot be controlled via DDC/CI: {}",
+            io::Error::last_os_error()
+        ),
+    )
+}
+
+fn get_active_display_config_paths() -> io::Result<Vec<DISPLAYCONFIG_PATH_INFO>> {
+    loop {
+        let mut num_paths: u32 = 0;
+        let mut num_modes: u32 = 0;
+        unsafe {
+            GetDisplayConfigBufferSizes(QDC_ONLY_ACTIVE_PATHS, &mut num_paths, &mut num_modes)
+                .ok()?;
+        }
+        let mut paths: Vec<DISPLAYCONFIG_PATH_INFO> = vec![Default::default(); num_paths as usize];
+        let mut modes: Vec<DISPLAYCONFIG_MODE_INFO> = vec![Default::default(); num_modes as usize];
+        let result = unsafe {
+            QueryDisplayConfig(
+                QDC_ONLY_ACTIVE_PATHS,
+                &mut num_paths,
+                paths.as_mut_ptr(),
+                &mut num_modes,
+                modes.as_mut_ptr(),
+                None,
+            )
+        };
+        match result {
+            // Display configuration was changed in between
+            ERROR_INSUFFICIENT_BUFFER => continue,
+            error => error.ok()?,
+        }
+        paths.truncate(num_paths as usize);
+        return Ok(paths);
+    }
+}
+
+fn display_config_get_device_info(header: &mut DISPLAYCONFIG_DEVICE_INFO_HEADER) -> io::Result<()> {
+    let result = unsafe { DisplayConfigGetDeviceInfo(header) };
+    WIN32_ERROR(result as u32).ok()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use more_asserts::*;
+
+    use super::*;
+
+    #[test]
+    fn check_screen_rects() -> io::Result<()> {
+        let primary_screen = get_primary_screen_rect();
+        let virtual_screen = get_virtual_screen_rect();
+        let work_area = get_work_area()?;
+        assert_gt!(primary_screen.right, 0);
+        assert_gt!(primary_screen.bottom, 0);
+        assert_le!(virtual_screen.left, primary_screen.left);
+        assert_ge!(virtual_screen.right, primary_screen.right);
+        assert_ge!(work_area.left, primary_screen.left);
+        assert_le!(work_area.bottom, primary_screen.bottom);
+        assert_ge!(get_system_metric(SystemMetric::MonitorCount), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn get_monitor_infos() -> io::Result<()> {
+        let monitors = MonitorHandle::get_all()?;
+        assert_ge!(monitors.len(), 1);
+        let primary_monitor = MonitorHandle::get_primary();
+        assert!(monitors.contains(&primary_monitor));
+        let info = primary_monitor.info()?;
+        assert!(info.is_primary);
+        assert_eq!(info.monitor_area, get_primary_screen_rect());
+        for monitor in &monitors {
+            let info = monitor.info()?;
+            assert_gt!(info.monitor_area.right - info.monitor_area.left, 0);
+            assert_gt!(info.monitor_area.bottom - info.monitor_area.top, 0);
+            assert!(!info.device_name.is_empty());
+        }
+        // Not available for some display adapters, e.g. with RDP or in virtual machines
+        if let Err(err) = primary_monitor.get_friendly_name() {
+            assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        }
+        // Fails for monitors without DDC/CI support
+        std::hint::black_box(&primary_monitor.get_brightness());
+        Ok(())
+    }
+}
@@ -5,6 +5,7 @@ use num_enum::{
 };
 use std::io;
 use windows::core::PCWSTR;
+use windows::Win32::Foundation::HWND;
 use windows::Win32::UI::WindowsAndMessaging::{
     MessageBoxExW,
     IDABORT,
@@ -30,7 +31,7 @@ use windows::Win32::UI::WindowsAndMessaging::{
     MESSAGEBOX_STYLE,
 };
 
-use crate::string::ToWideString;
+use crate::string::ZeroTerminatedWideString;
 use crate::ui::WindowHandle;
 
 #[derive(Copy, Clone, Default, Debug)]
@@ -92,21 +93,50 @@ pub enum PressedMessageBoxButton {
     Other(i32),
 }
 
+/// Shows a modal message box and waits for the user to press a button.
+///
+/// If an owner window is given, the message box will be modal to it.
+///
+/// # Examples
+///
+/// ```no_run
+/// use winapi_easy::ui::message_box::{
+///     show_message_box,
+///     MessageBoxButtons,
+///     MessageBoxOptions,
+///     PressedMessageBoxButton,
+/// };
+///
+/// let pressed_button = show_message_box(
+///     None,
+///     MessageBoxOptions {
+///         message: Some("Continue?"),
+///         caption: Some("Question"),
+///         buttons: MessageBoxButtons::YesNo,
+///         ..Default::default()
+///     },
+/// )?;
+/// if pressed_button == PressedMessageBoxButton::Yes {
+///     // ...
+/// }
+///
+/// # Result::<(), std::io::Error>::Ok(())
+/// ```
 pub fn show_message_box(
-    window_handle: &WindowHandle,
+    owner: Option<&WindowHandle>,
     options: MessageBoxOptions,
 ) -> io::Result<PressedMessageBoxButton> {
+    let message = options.message.map(ZeroTerminatedWideString::from_os_str);
+    let caption = options.caption.map(ZeroTerminatedWideString::from_os_str);
     let result = unsafe {
         MessageBoxExW(
-            window_handle.raw_handle,
-            options
-                .message
-                .map(|x| PCWSTR::from_raw(x.to_wide_string().as_ptr()))
-                .as_ref(),
-            options
-                .caption
-                .map(|x| PCWSTR::from_raw(x.to_wide_string().as_ptr()))
-                .as_ref(),
+            owner.map(HWND::from).unwrap_or_default(),
+            message
+                .as_ref()
+                .map_or(PCWSTR::null(), ZeroTerminatedWideString::as_raw_pcwstr),
+            caption
+                .as_ref()
+                .map_or(PCWSTR::null(), ZeroTerminatedWideString::as_raw_pcwstr),
             MESSAGEBOX_STYLE::from(options.buttons)
                 | options.icon.map(MESSAGEBOX_STYLE::from).unwrap_or_default(),
             0,
@@ -1,34 +1,151 @@
 //! Window and thread message handling.
 
-use std::io;
+use std::cell::Cell;
+use std::path::PathBuf;
+use std::{
+    io,
+    mem,
+    ptr,
+    slice,
+};
+
+use num_enum::{
+    FromPrimitive,
+    IntoPrimitive,
+};
+use windows::core::GUID;
 
+#[cfg(feature = "input")]
+use windows::Win32::Foundation::HANDLE;
 use windows::Win32::Foundation::{
     HWND,
     LPARAM,
     LRESULT,
     WPARAM,
 };
-use windows::Win32::UI::Shell::NIN_SELECT;
+use windows::Win32::Graphics::Gdi::ScreenToClient;
+use windows::Win32::UI::HiDpi::PhysicalToLogicalPointForPerMonitorDPI;
+#[cfg(feature = "input")]
+use windows::Win32::UI::Input::HRAWINPUT;
+use windows::Win32::UI::Shell::{
+    DragFinish,
+    DragQueryPoint,
+    HDROP,
+    NIN_BALLOONHIDE,
+    NIN_BALLOONSHOW,
+    NIN_BALLOONTIMEOUT,
+    NIN_BALLOONUSERCLICK,
+    NIN_POPUPCLOSE,
+    NIN_POPUPOPEN,
+    NIN_SELECT,
+};
 use windows::Win32::UI::WindowsAndMessaging::{
     DefWindowProcW,
     GetMessagePos,
     PostMessageW,
+    DBT_DEVICEARRIVAL,
+    DBT_DEVICEREMOVECOMPLETE,
+    DBT_DEVTYP_DEVICEINTERFACE,
+    DBT_DEVTYP_VOLUME,
+    DEV_BROADCAST_DEVICEINTERFACE_W,
+    DEV_BROADCAST_HDR,
+    DEV_BROADCAST_VOLUME,
     HMENU,
+    HTBOTTOM,
+    HTBOTTOMLEFT,
+    HTBOTTOMRIGHT,
+    HTCAPTION,
+    HTCLIENT,
+    HTCLOSE,
+    HTLEFT,
+    HTMAXBUTTON,
+    HTMINBUTTON,
+    HTNOWHERE,
+    HTRIGHT,
+    HTSYSMENU,
+    HTTOP,
+    HTTOPLEFT,
+    HTTOPRIGHT,
+    HTTRANSPARENT,
+    PBT_APMPOWERSTATUSCHANGE,
+    PBT_APMRESUMEAUTOMATIC,
+    PBT_APMRESUMESUSPEND,
+    PBT_APMSUSPEND,
     SIZE_MINIMIZED,
     WM_APP,
+    WM_CHAR,
     WM_CLOSE,
     WM_CONTEXTMENU,
     WM_DESTROY,
+    WM_DEVICECHANGE,
+    WM_DPICHANGED,
+    WM_DROPFILES,
+    WM_HOTKEY,
     WM_MENUCOMMAND,
+    WM_NCHITTEST,
+    WM_PAINT,
+    WM_POWERBROADCAST,
     WM_SIZE,
+    WM_WTSSESSION_CHANGE,
+    WTS_CONSOLE_CONNECT,
+    WTS_CONSOLE_DISCONNECT,
+    WTS_REMOTE_CONNECT,
+    WTS_REMOTE_DISCONNECT,
+    WTS_SESSION_CREATE,
+    WTS_SESSION_LOCK,
+    WTS_SESSION_LOGOFF,
+    WTS_SESSION_LOGON,
+    WTS_SESSION_REMOTE_CONTROL,
+    WTS_SESSION_TERMINATE,
+    WTS_SESSION_UNLOCK,
+};
+#[cfg(feature = "input")]
+use windows::Win32::UI::WindowsAndMessaging::{
+    GIDC_ARRIVAL,
+    GIDC_REMOVAL,
+    WM_INPUT,
+    WM_INPUT_DEVICE_CHANGE,
+    WM_KEYDOWN,
+    WM_KEYUP,
+    WM_LBUTTONDBLCLK,
+    WM_LBUTTONDOWN,
+    WM_LBUTTONUP,
+    WM_MBUTTONDBLCLK,
+    WM_MBUTTONDOWN,
+    WM_MBUTTONUP,
+    WM_MOUSEMOVE,
+    WM_RBUTTONDBLCLK,
+    WM_RBUTTONDOWN,
+    WM_RBUTTONUP,
+    WM_XBUTTONDBLCLK,
+    WM_XBUTTONDOWN,
+    WM_XBUTTONUP,
 };
 
-use crate::internal::catch_unwind_and_abort;
+#[cfg(feature = "input")]
+use crate::input::raw_input::{
+    RawInput,
+    RawInputDeviceHandle,
+};
+#[cfg(feature = "input")]
+use crate::input::{
+    KeyboardKey,
+    MouseButton,
+};
 use crate::internal::windows_missing::*;
+use crate::internal::{
+    catch_unwind_and_abort,
+    get_hdrop_file_list,
+};
 use crate::messaging::ThreadMessageLoop;
+use crate::string::FromWideString;
 use crate::ui::menu::MenuHandle;
+use crate::ui::paint::PaintContext;
 use crate::ui::{
+    hide_notification_popup,
+    show_notification_popup,
     Point,
+    Rectangle,
     WindowHandle,
 };
 
@@ -51,6 +168,159 @@ impl ListenerAnswer {
     }
 }
 
+/// An event concerning a balloon notification of a notification icon.
+///
+/// See also: [`crate::ui::NotificationIcon::set_balloon_notification`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum NotificationBalloonEvent {
+    Shown,
+    /// The balloon was hidden for a reason other than a timeout or a click, e.g. because the icon was removed.
+    Hidden,
+    /// The balloon was dismissed by the user or closed after the timeout.
+    TimedOut,
+    Clicked,
+}
+
+/// The part of a window at a certain point, see [`WindowMessageListener::handle_hit_test`].
+///
+/// Windows uses this to decide how to react to the mouse, e.g. dragging the window when
+/// the caption area is pressed or resizing it when one of the borders is.
+#[derive(IntoPrimitive, Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(i32)]
+pub enum HitTestArea {
+    /// The desktop or a dividing line between windows.
+    Nowhere = HTNOWHERE as i32,
+    Client = HTCLIENT as i32,
+    /// The title bar, allowing the window to be moved by dragging.
+    Caption = HTCAPTION as i32,
+    SystemMenu = HTSYSMENU as i32,
+    MinimizeButton = HTMINBUTTON as i32,
+    /// The maximize button.
+    ///
+    /// On Windows 11, hovering an area reported as this shows the snap layouts flyout,
+    /// which also works for custom-drawn buttons if the window has the [`crate::ui::WindowStyle::MaximizeBox`] style.
+    MaximizeButton = HTMAXBUTTON as i32,
+    CloseButton = HTCLOSE as i32,
+    LeftBorder = HTLEFT as i32,
+    RightBorder = HTRIGHT as i32,
+    TopBorder = HTTOP as i32,
+    BottomBorder = HTBOTTOM as i32,
+    TopLeftCorner = HTTOPLEFT as i32,
+    TopRightCorner = HTTOPRIGHT as i32,
+    BottomLeftCorner = HTBOTTOMLEFT as i32,
+    BottomRightCorner = HTBOTTOMRIGHT as i32,
+    /// Covered by another window of the same thread, which then receives the mouse input instead.
+    Transparent = HTTRANSPARENT,
+}
+
+/// A change of a user session or its lock state, see [`crate::ui::SessionNotifications`].
+#[derive(FromPrimitive, Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(u32)]
+pub enum SessionChangeEvent {
+    /// The session was connected to the console terminal, e.g. by switching users.
+    ConsoleConnect = WTS_CONSOLE_CONNECT,
+    /// The session was disconnected from the console terminal.
+    ConsoleDisconnect = WTS_CONSOLE_DISCONNECT,
+    RemoteConnect = WTS_REMOTE_CONNECT,
+    RemoteDisconnect = WTS_REMOTE_DISCONNECT,
+    Logon = WTS_SESSION_LOGON,
+    Logoff = WTS_SESSION_LOGOFF,
+    Lock = WTS_SESSION_LOCK,
+    Unlock = WTS_SESSION_UNLOCK,
+    /// The remote control status of the session changed.
+    RemoteControl = WTS_SESSION_REMOTE_CONTROL,
+    Create = WTS_SESSION_CREATE,
+    Terminate = WTS_SESSION_TERMINATE,
+    #[num_enum(catch_all)]
+    Other(u32),
+}
+
+/// A power management event, see [`WindowMessageListener::handle_power_event`].
+#[derive(FromPrimitive, Copy, Clone, Eq, PartialEq, Debug)]
+#[repr(u32)]
+pub enum PowerEvent {
+    /// The power status changed, e.g. switching between AC and battery power or a change of the battery level.
+    PowerStatusChange = PBT_APMPOWERSTATUSCHANGE,
+    /// The system is about to be suspended.
+    Suspend = PBT_APMSUSPEND,
+    /// The system resumed from suspension, regardless of user activity.
+    ResumeAutomatic = PBT_APMRESUMEAUTOMATIC,
+    /// The system resumed from suspension because of user activity, sent after [`Self::ResumeAutomatic`].
+    ResumeSuspend = PBT_APMRESUMESUSPEND,
+    #[num_enum(catch_all)]
+    Other(u32),
+}
+
+/// The kind of a device change, see [`WindowMessageListener::handle_device_change`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum DeviceChangeKind {
+    /// A device was inserted and is now available.
+    Arrival,
+    /// A device was removed.
+    RemoveComplete,
+}
+
+/// The device affected by a device change, see [`WindowMessageListener::handle_device_change`].
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum ChangedDevice {
+    /// A logical volume, e.g. a USB drive or a CD.
+    Volume {
+        /// The drive letters of the volume, e.g. `'E'`.
+        drive_letters: Vec<char>,
+    },
+    /// A device interface, only reported with [`crate::ui::DeviceNotifications`].
+    DeviceInterface {
+        class_guid: GUID,
+        /// The device path, which can be used to open the device.
+        name: String,
+    },
+    Other,
+}
+
+impl ChangedDevice {
+    /// Decodes a `DEV_BROADCAST_HDR` and the data following it.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must point to a valid broadcast structure.
+    unsafe fn from_raw(raw_header: *const DEV_BROADCAST_HDR) -> Self {
+        let header = unsafe { *raw_header };
+        match header.dbch_devicetype {
+            DBT_DEVTYP_VOLUME => {
+                let volume = unsafe { *raw_header.cast::<DEV_BROADCAST_VOLUME>() };
+                let drive_letters = (0..26u8)
+                    .filter(|index| volume.dbcv_unitmask & (1 << index) != 0)
+                    .map(|index| char::from(b'A' + index))
+                    .collect();
+                ChangedDevice::Volume { drive_letters }
+            }
+            DBT_DEVTYP_DEVICEINTERFACE => {
+                let interface = raw_header.cast::<DEV_BROADCAST_DEVICEINTERFACE_W>();
+                let name_offset = mem::offset_of!(DEV_BROADCAST_DEVICEINTERFACE_W, dbcc_name);
+                // The name is a variable length zero terminated string, possibly with padding after it
+                let max_name_len =
+                    (header.dbch_size as usize).saturating_sub(name_offset) / mem::size_of::<u16>();
+                let name_chars = unsafe {
+                    slice::from_raw_parts(
+                        ptr::addr_of!((*interface).dbcc_name).cast::<u16>(),
+                        max_name_len,
+                    )
+                };
+                let name_len = name_chars
+                    .iter()
+                    .position(|char| *char == 0)
+                    .unwrap_or(max_name_len);
+                let name_chars = &name_chars[..name_len];
+                ChangedDevice::DeviceInterface {
+                    class_guid: unsafe { (*interface).dbcc_classguid },
+                    name: name_chars.to_string_lossy(),
+                }
+            }
+            _ => ChangedDevice::Other,
+        }
+    }
+}
+
 /// A user-defined implementation for various windows message handlers.
 ///
 /// The trait already defines a default for all methods, making it easier to just implement specific ones.
@@ -82,13 +352,162 @@ pub trait WindowMessageListener {
     #[inline(always)]
     fn handle_window_destroy(&self, window: &WindowHandle) {}
     /// A notification icon was selected (triggered).
+    ///
+    /// For icons using [`crate::ui::NotificationIconVersion::Version4`], the coordinates are the position
+    /// of the event as reported by the shell, which is the icon position for keyboard selections.
+    /// Otherwise they are the position of the mouse cursor.
     #[allow(unused_variables)]
     #[inline(always)]
     fn handle_notification_icon_select(&self, icon_id: u16, xy_coords: Point) {}
     /// A notification icon was context-selected (e.g. right-clicked).
+    ///
+    /// The coordinates behave like with [`Self::handle_notification_icon_select`].
     #[allow(unused_variables)]
     #[inline(always)]
     fn handle_notification_icon_context_select(&self, icon_id: u16, xy_coords: Point) {}
+    /// The mouse cursor started hovering over a notification icon.
+    ///
+    /// This is the point to show a custom pop-up UI, which should be closed again on
+    /// [`Self::handle_notification_icon_hover_end`]. Only sent for icons without tooltip text,
+    /// since the standard tooltip is used otherwise.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_notification_icon_hover(&self, icon_id: u16, xy_coords: Point) {}
+    /// The mouse cursor stopped hovering over a notification icon.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_notification_icon_hover_end(&self, icon_id: u16) {}
+    /// A balloon notification of a notification icon was shown, hidden or clicked.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_notification_balloon_event(&self, icon_id: u16, event: NotificationBalloonEvent) {}
+    /// A mouse button was pressed, released or double-clicked inside the window's client area.
+    ///
+    /// Double-clicks are only reported if the window class was registered with
+    /// [`crate::ui::WindowClassStyle::DoubleClicks`].
+    #[cfg(feature = "input")]
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_mouse_button(
+        &self,
+        window: &WindowHandle,
+        button: MouseButton,
+        action: MouseButtonAction,
+        client_coords: Point,
+    ) {
+    }
+    /// The mouse cursor was moved inside the window's client area.
+    ///
+    /// Due to its frequency, this message does not trigger the thread message loop callback.
+    #[cfg(feature = "input")]
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_mouse_move(&self, window: &WindowHandle, client_coords: Point) {}
+    /// A keyboard key was pressed or released while the window had the keyboard focus.
+    ///
+    /// Keys pressed together with Alt (system keys) are not reported here.
+    #[cfg(feature = "input")]
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_keyboard_key(&self, window: &WindowHandle, key: KeyboardKey, action: KeyAction) {}
+    /// A character was entered while the window had the keyboard focus.
+    ///
+    /// The character is the result of translating key presses using the current keyboard layout.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_char_input(&self, window: &WindowHandle, character: char) {}
+    /// The window's client area needs to be painted, e.g. after [`WindowHandle::redraw`].
+    ///
+    /// Drawing must only be performed inside of [`PaintContext::get_invalidated_area`].
+    ///
+    /// This message does not trigger the thread message loop callback, since repainting is frequent
+    /// and should not cause further changes.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_paint(&self, window: &WindowHandle, paint_context: &PaintContext) {}
+    /// Windows asks which part of the window is at the given point, e.g. when the mouse is moved.
+    ///
+    /// Returning `None` uses the default handling based on the window's frame. A borderless window
+    /// can return [`HitTestArea::Caption`] or one of the borders to allow the user to move or resize it.
+    /// Returning [`HitTestArea::MaximizeButton`] for a custom maximize button enables the Windows 11 snap layouts.
+    ///
+    /// The point is in client coordinates and may be outside of the client area.
+    /// Due to its frequency, this message does not trigger the thread message loop callback.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_hit_test(&self, window: &WindowHandle, client_coords: Point) -> Option<HitTestArea> {
+        None
+    }
+    /// A hotkey registered for this window was pressed.
+    ///
+    /// This only concerns hotkeys registered with the window's handle using `RegisterHotKey`,
+    /// not the ones from `GlobalHotkeySet`, which use their own thread.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_hotkey(&self, window: &WindowHandle, hotkey_id: i32) {}
+    /// The DPI of the window changed, e.g. because it was moved to a monitor with a different scaling factor.
+    ///
+    /// Only sent to windows of processes that are per-monitor DPI aware. The suggested area is the new window
+    /// size and position in screen coordinates as recommended by Windows, which the listener should usually apply.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_dpi_changed(&self, window: &WindowHandle, new_dpi: u32, suggested_area: Rectangle) {}
+    /// Files were dropped onto the window using drag & drop.
+    ///
+    /// Requires [`WindowHandle::set_accept_files`]. The drop point is in client coordinates.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_files_dropped(&self, window: &WindowHandle, paths: &[PathBuf], drop_point: Point) {}
+    /// A power management event occurred, e.g. a change between AC and battery power.
+    ///
+    /// Sent to all top-level windows. The current status can be queried with `power::get_system_power_status`.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_power_event(&self, window: &WindowHandle, event: PowerEvent) {}
+    /// A device was inserted or removed.
+    ///
+    /// Volumes like USB drives are reported to all top-level windows. For other devices,
+    /// [`crate::ui::DeviceNotifications`] is required.
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_device_change(
+        &self,
+        window: &WindowHandle,
+        kind: DeviceChangeKind,
+        device: &ChangedDevice,
+    ) {
+    }
+    /// A user session was changed, e.g. the workstation was locked or unlocked.
+    ///
+    /// Requires [`crate::ui::SessionNotifications`].
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_session_change(
+        &self,
+        window: &WindowHandle,
+        event: SessionChangeEvent,
+        session_id: u32,
+    ) {
+    }
+    /// Raw input was received from a device registered with [`crate::input::raw_input::register`].
+    #[cfg(feature = "input")]
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_raw_input(&self, window: &WindowHandle, input: &RawInput) {}
+    /// A raw input device was added or removed.
+    ///
+    /// Requires registering with [`crate::input::raw_input::RawInputOptions::receive_device_changes`].
+    /// Devices already present during registration are also reported as added.
+    #[cfg(feature = "input")]
+    #[allow(unused_variables)]
+    #[inline(always)]
+    fn handle_input_device_change(
+        &self,
+        window: &WindowHandle,
+        added: bool,
+        device: RawInputDeviceHandle,
+    ) {
+    }
     /// A custom user message was sent.
     #[allow(unused_variables)]
     #[inline(always)]
@@ -102,6 +521,24 @@ pub trait WindowMessageListener {
     }
 }
 
+/// What happened to a mouse button in [`WindowMessageListener::handle_mouse_button`].
+#[cfg(feature = "input")]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum MouseButtonAction {
+    Down,
+    Up,
+    DoubleClick,
+}
+
+/// What happened to a key in [`WindowMessageListener::handle_keyboard_key`].
+#[cfg(feature = "input")]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum KeyAction {
+    /// A key press, possibly auto-repeated while the key is held down.
+    KeyDown,
+    KeyUp,
+}
+
 /// A [`WindowMessageListener`] that leaves all handlers to their default empty impls.
 #[derive(Copy, Clone, Default, Debug)]
 pub struct EmptyWindowMessageListener;
@@ -124,6 +561,12 @@ impl RawMessage {
 
     pub(crate) const ID_APP_WAKEUP_MSG: u32 = Self::STR_MSG_RANGE_START - 1;
     pub(crate) const ID_NOTIFICATION_ICON_MSG: u32 = Self::STR_MSG_RANGE_START - 2;
+    pub(crate) const ID_NOTIFICATION_ICON_LEGACY_MSG: u32 = Self::STR_MSG_RANGE_START - 3;
+
+    thread_local! {
+        /// First half of a UTF-16 surrogate pair received via `WM_CHAR`, waiting for the second half.
+        static PENDING_HIGH_SURROGATE: Cell<Option<u16>> = const { Cell::new(None) };
+    }
 
     pub(crate) fn dispatch_to_message_listener<WML: WindowMessageListener>(
         self,
@@ -144,20 +587,31 @@ impl RawMessage {
                 );
                 None
             }
-            Self::ID_NOTIFICATION_ICON_MSG => {
-                let icon_id =
-                    HIWORD(u32::try_from(self.l_param.0).expect("Icon ID conversion failed"));
+            Self::ID_NOTIFICATION_ICON_MSG | Self::ID_NOTIFICATION_ICON_LEGACY_MSG => {
+                let icon_id = if self.message == Self::ID_NOTIFICATION_ICON_MSG {
+                    HIWORD(u32::try_from(self.l_param.0).expect("Icon ID conversion failed"))
+                } else {
+                    // Legacy versions send the icon ID in `w_param`
+                    LOWORD(u32::try_from(self.w_param.0).expect("Icon ID conversion failed"))
+                };
                 let event_code: u32 =
                     LOWORD(u32::try_from(self.l_param.0).expect("Event code conversion failed"))
                         .into();
-                let xy_coords = {
-                    // `w_param` does contain the coordinates of the click event, but they are not adjusted for DPI scaling, so we can't use them.
-                    // Instead we have to call `GetMessagePos`, which will however return mouse coordinates even if the keyboard was used.
-                    // An alternative would be to use `NOTIFYICON_VERSION_4`, but that would not allow exposing an API for rich pop-up UIs
-                    // when the user hovers over the tray icon since the necessary notifications would not be sent.
+                let xy_coords = if self.message == Self::ID_NOTIFICATION_ICON_MSG {
+                    // With version 4, `w_param` contains the anchor coordinates of the event, which is the icon position
+                    // if the keyboard was used. They are physical coordinates, so they have to be converted
+                    // if the window is not per-monitor DPI aware.
+                    let mut anchor = get_param_xy_coords(LPARAM(self.w_param.0 as isize));
+                    let _ = unsafe {
+                        PhysicalToLogicalPointForPerMonitorDPI(HWND::from(&window), &mut anchor)
+                    };
+                    anchor
+                } else {
+                    // Legacy versions don't send coordinates, so we have to call `GetMessagePos`,
+                    // which will however return mouse coordinates even if the keyboard was used.
                     // See also: https://stackoverflow.com/a/41649787
                     let raw_position = unsafe { GetMessagePos() };
-                    get_param_xy_coords(raw_position)
+                    get_param_xy_coords(LPARAM(raw_position.try_into().unwrap()))
                 };
                 match event_code {
                     // NIN_SELECT only happens with left clicks. Space will produce 1x NIN_KEYSELECT, Enter 2x NIN_KEYSELECT.
@@ -168,6 +622,30 @@ impl RawMessage {
                     WM_CONTEXTMENU => {
                         listener.handle_notification_icon_context_select(icon_id, xy_coords)
                     }
+                    NIN_POPUPOPEN => {
+                        listener.handle_notification_icon_hover(icon_id, xy_coords);
+                        let _ = show_notification_popup(&window, icon_id, xy_coords);
+                    }
+                    NIN_POPUPCLOSE => {
+                        listener.handle_notification_icon_hover_end(icon_id);
+                        hide_notification_popup(&window, icon_id);
+                    }
+                    NIN_BALLOONSHOW => listener.handle_notification_balloon_event(
+                        icon_id,
+                        NotificationBalloonEvent::Shown,
+                    ),
+                    NIN_BALLOONHIDE => listener.handle_notification_balloon_event(
+                        icon_id,
+                        NotificationBalloonEvent::Hidden,
+                    ),
+                    NIN_BALLOONTIMEOUT => listener.handle_notification_balloon_event(
+                        icon_id,
+                        NotificationBalloonEvent::TimedOut,
+                    ),
+                    NIN_BALLOONUSERCLICK => listener.handle_notification_balloon_event(
+                        icon_id,
+                        NotificationBalloonEvent::Clicked,
+                    ),
                     _ => (),
                 }
                 None
@@ -188,6 +666,134 @@ impl RawMessage {
                 }
                 None
             }
+            #[cfg(feature = "input")]
+            WM_LBUTTONDOWN | WM_LBUTTONUP | WM_LBUTTONDBLCLK | WM_RBUTTONDOWN | WM_RBUTTONUP
+            | WM_RBUTTONDBLCLK | WM_MBUTTONDOWN | WM_MBUTTONUP | WM_MBUTTONDBLCLK
+            | WM_XBUTTONDOWN | WM_XBUTTONUP | WM_XBUTTONDBLCLK => {
+                let (button, action) = self.get_mouse_button_action();
+                listener.handle_mouse_button(
+                    &window,
+                    button,
+                    action,
+                    get_param_xy_coords(self.l_param),
+                );
+                None
+            }
+            #[cfg(feature = "input")]
+            WM_MOUSEMOVE => {
+                listener.handle_mouse_move(&window, get_param_xy_coords(self.l_param));
+                call_message_loop_callback = false;
+                None
+            }
+            #[cfg(feature = "input")]
+            WM_KEYDOWN | WM_KEYUP => {
+                let key = KeyboardKey::from(LOWORD(self.w_param.0 as u32));
+                let action = if self.message == WM_KEYDOWN {
+                    KeyAction::KeyDown
+                } else {
+                    KeyAction::KeyUp
+                };
+                listener.handle_keyboard_key(&window, key, action);
+                None
+            }
+            WM_CHAR => {
+                if let Some(character) = self.get_char() {
+                    listener.handle_char_input(&window, character);
+                }
+                None
+            }
+            WM_PAINT => {
+                call_message_loop_callback = false;
+                PaintContext::begin(&window).map(|paint_context| {
+                    listener.handle_paint(&window, &paint_context);
+                    LRESULT(0)
+                })
+            }
+            WM_NCHITTEST => {
+                let mut coords = get_param_xy_coords(self.l_param);
+                unsafe {
+                    let _ = ScreenToClient(HWND::from(&window), &mut coords);
+                }
+                call_message_loop_callback = false;
+                listener
+                    .handle_hit_test(&window, coords)
+                    .map(|area| LRESULT(i32::from(area) as isize))
+            }
+            WM_HOTKEY => {
+                listener.handle_hotkey(&window, self.w_param.0 as i32);
+                None
+            }
+            WM_DPICHANGED => {
+                // X and Y DPI are always identical for windows apps
+                let new_dpi = HIWORD(self.w_param.0 as u32).into();
+                let suggested_area = unsafe { *(self.l_param.0 as *const Rectangle) };
+                listener.handle_dpi_changed(&window, new_dpi, suggested_area);
+                None
+            }
+            WM_DROPFILES => {
+                let hdrop = HDROP(self.w_param.0 as *mut std::ffi::c_void);
+                let mut drop_point = Point::default();
+                let paths = unsafe {
+                    let _ = DragQueryPoint(hdrop, &mut drop_point);
+                    let paths = get_hdrop_file_list(hdrop);
+                    DragFinish(hdrop);
+                    paths
+                };
+                if let Ok(paths) = paths {
+                    listener.handle_files_dropped(&window, &paths, drop_point);
+                }
+                Some(LRESULT(0))
+            }
+            WM_DEVICECHANGE => {
+                let maybe_kind = match self.w_param.0 as u32 {
+                    DBT_DEVICEARRIVAL => Some(DeviceChangeKind::Arrival),
+                    DBT_DEVICEREMOVECOMPLETE => Some(DeviceChangeKind::RemoveComplete),
+                    _ => None,
+                };
+                if let Some(kind) = maybe_kind {
+                    if self.l_param.0 != 0 {
+                        let device = unsafe {
+                            ChangedDevice::from_raw(self.l_param.0 as *const DEV_BROADCAST_HDR)
+                        };
+                        listener.handle_device_change(&window, kind, &device);
+                    }
+                }
+                None
+            }
+            WM_POWERBROADCAST => {
+                listener.handle_power_event(&window, PowerEvent::from(self.w_param.0 as u32));
+                None
+            }
+            WM_WTSSESSION_CHANGE => {
+                let event = SessionChangeEvent::from(self.w_param.0 as u32);
+                listener.handle_session_change(&window, event, self.l_param.0 as u32);
+                None
+            }
+            #[cfg(feature = "input")]
+            WM_INPUT => {
+                if let Ok(input) =
+                    RawInput::from_handle(HRAWINPUT(self.l_param.0 as *mut std::ffi::c_void))
+                {
+                    listener.handle_raw_input(&window, &input);
+                }
+                // `DefWindowProcW` must be called for cleanup
+                None
+            }
+            #[cfg(feature = "input")]
+            WM_INPUT_DEVICE_CHANGE => {
+                let maybe_added = match self.w_param.0 as u32 {
+                    GIDC_ARRIVAL => Some(true),
+                    GIDC_REMOVAL => Some(false),
+                    _ => None,
+                };
+                if let Some(added) = maybe_added {
+                    let device = RawInputDeviceHandle::from_raw(HANDLE(
+                        self.l_param.0 as *mut std::ffi::c_void,
+                    ));
+                    listener.handle_input_device_change(&window, added, device);
+                }
+                None
+            }
             WM_CLOSE => listener.handle_window_close(&window).to_raw_lresult(),
             WM_DESTROY => {
                 listener.handle_window_destroy(&window);
@@ -204,6 +810,55 @@ impl RawMessage {
         result
     }
 
+    /// Decodes the UTF-16 code unit of a `WM_CHAR` message.
+    ///
+    /// Characters outside of the BMP arrive as two consecutive messages, so the first half is stored
+    /// until the second one arrives.
+    fn get_char(&self) -> Option<char> {
+        let code_unit = LOWORD(self.w_param.0 as u32);
+        let pending = Self::PENDING_HIGH_SURROGATE.with(|x| x.take());
+        if (0xD800..0xDC00).contains(&code_unit) {
+            Self::PENDING_HIGH_SURROGATE.with(|x| x.set(Some(code_unit)));
+            return None;
+        }
+        let code_units: &[u16] = match pending {
+            Some(high_surrogate) if (0xDC00..0xE000).contains(&code_unit) => {
+                &[high_surrogate, code_unit]
+            }
+            _ => &[code_unit],
+        };
+        char::decode_utf16(code_units.iter().copied())
+            .next()
+            .and_then(Result::ok)
+    }
+
+    #[cfg(feature = "input")]
+    fn get_mouse_button_action(&self) -> (MouseButton, MouseButtonAction) {
+        let x_button = || {
+            // The X button is in the high word of `w_param`, with `XBUTTON1 == 1` and `XBUTTON2 == 2`
+            if HIWORD(self.w_param.0 as u32) == 2 {
+                MouseButton::X2
+            } else {
+                MouseButton::X1
+            }
+        };
+        match self.message {
+            WM_LBUTTONDOWN => (MouseButton::Left, MouseButtonAction::Down),
+            WM_LBUTTONUP => (MouseButton::Left, MouseButtonAction::Up),
+            WM_LBUTTONDBLCLK => (MouseButton::Left, MouseButtonAction::DoubleClick),
+            WM_RBUTTONDOWN => (MouseButton::Right, MouseButtonAction::Down),
+            WM_RBUTTONUP => (MouseButton::Right, MouseButtonAction::Up),
+            WM_RBUTTONDBLCLK => (MouseButton::Right, MouseButtonAction::DoubleClick),
+            WM_MBUTTONDOWN => (MouseButton::Middle, MouseButtonAction::Down),
+            WM_MBUTTONUP => (MouseButton::Middle, MouseButtonAction::Up),
+            WM_MBUTTONDBLCLK => (MouseButton::Middle, MouseButtonAction::DoubleClick),
+            WM_XBUTTONDOWN => (x_button(), MouseButtonAction::Down),
+            WM_XBUTTONUP => (x_button(), MouseButtonAction::Up),
+            WM_XBUTTONDBLCLK => (x_button(), MouseButtonAction::DoubleClick),
+            _ => unreachable!("Not a mouse button message: {}", self.message),
+        }
+    }
+
     /// Posts a message to the thread message queue and returns immediately.
     ///
     /// If no window is given, the window procedure won't be called by `DispatchMessageW`.
@@ -263,8 +918,7 @@ where
     catch_unwind_and_abort(call)
 }
 
-fn get_param_xy_coords(param: u32) -> Point {
-    let param = LPARAM(param.try_into().unwrap());
+fn get_param_xy_coords(param: LPARAM) -> Point {
     Point {
         x: GET_X_LPARAM(param),
         y: GET_Y_LPARAM(param),
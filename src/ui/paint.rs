@@ -0,0 +1,339 @@
+//! Painting the client area of windows.
+
+use std::io;
+use std::marker::PhantomData;
+
+use windows::Win32::Foundation::{
+    COLORREF,
+    HWND,
+};
+use windows::Win32::Graphics::Gdi::{
+    BeginPaint,
+    CreateEllipticRgnIndirect,
+    CreatePen,
+    CreateRectRgnIndirect,
+    CreateRoundRectRgn,
+    DeleteObject,
+    DrawTextW,
+    EndPaint,
+    FillRect,
+    GetDC,
+    GetRgnBox,
+    LineTo,
+    MoveToEx,
+    PtInRegion,
+    ReleaseDC,
+    SelectObject,
+    SetBkMode,
+    SetTextColor,
+    BACKGROUND_MODE,
+    DT_NOPREFIX,
+    DT_WORDBREAK,
+    HDC,
+    HRGN,
+    PAINTSTRUCT,
+    PS_SOLID,
+    TRANSPARENT,
+};
+
+use crate::internal::ReturnValue;
+use crate::string::ToWideString;
+use crate::ui::resource::Brush;
+use crate::ui::{
+    Point,
+    Rectangle,
+    WindowHandle,
+};
+
+/// A device context for painting a window's invalidated area, valid during
+/// [`crate::ui::messaging::WindowMessageListener::handle_paint`].
+///
+/// Painting is finished and the invalidated area validated again once the listener returns.
+#[derive(Debug)]
+pub struct PaintContext<'a> {
+    window: HWND,
+    paint_struct: PAINTSTRUCT,
+    phantom: PhantomData<&'a WindowHandle>,
+}
+
+impl<'a> PaintContext<'a> {
+    pub(crate) fn begin(window: &'a WindowHandle) -> Option<Self> {
+        let mut paint_struct: PAINTSTRUCT = Default::default();
+        let hdc = unsafe { BeginPaint(HWND::from(window), &mut paint_struct) };
+        if hdc.is_invalid() {
+            None
+        } else {
+            Some(Self {
+                window: window.into(),
+                paint_struct,
+                phantom: PhantomData,
+            })
+        }
+    }
+
+    /// Returns the area of the client area that needs to be painted, in client coordinates.
+    pub fn get_invalidated_area(&self) -> Rectangle {
+        self.paint_struct.rcPaint
+    }
+
+    /// Returns `true` if the background was not erased yet and has to be painted by the listener.
+    ///
+    /// This is usually only the case if the window class has no background brush.
+    pub fn needs_background_erase(&self) -> bool {
+        self.paint_struct.fErase.as_bool()
+    }
+
+    /// Fills a rectangle using the given brush.
+    pub fn fill_rect(&self, area: Rectangle, brush: &impl Brush) -> io::Result<()> {
+        fill_rect(self.into(), area, brush)
+    }
+
+    /// Draws text with a transparent background, wrapping lines at the borders of the given area.
+    pub fn draw_text(&self, area: Rectangle, text: &str, color: Rgb) -> io::Result<()> {
+        draw_text(self.into(), area, text, color)
+    }
+
+    /// Draws a solid line with a width of 1 pixel.
+    pub fn draw_line(&self, from: Point, to: Point, color: Rgb) -> io::Result<()> {
+        draw_line(self.into(), from, to, color)
+    }
+}
+
+impl Drop for PaintContext<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = EndPaint(self.window, &self.paint_struct);
+        }
+    }
+}
+
+impl From<&PaintContext<'_>> for HDC {
+    /// Returns the underlying raw device context handle used by [`windows`].
+    fn from(value: &PaintContext<'_>) -> Self {
+        value.paint_struct.hdc
+    }
+}
+
+/// A device context for drawing on a window's client area outside of
+/// [`crate::ui::messaging::WindowMessageListener::handle_paint`], see [`WindowHandle::get_dc`].
+///
+/// Useful for continuously updated content. Anything drawn this way will be overwritten
+/// by the next regular paint of the window. The device context is released on drop.
+#[derive(Debug)]
+pub struct WindowDc<'a> {
+    window: HWND,
+    hdc: HDC,
+    phantom: PhantomData<&'a WindowHandle>,
+}
+
+impl<'a> WindowDc<'a> {
+    pub(crate) fn get(window: &'a WindowHandle) -> io::Result<Self> {
+        let hdc = unsafe { GetDC(HWND::from(window)) };
+        if hdc.is_invalid() {
+            return Err(io::ErrorKind::Other.into());
+        }
+        Ok(Self {
+            window: window.into(),
+            hdc,
+            phantom: PhantomData,
+        })
+    }
+
+    /// Fills a rectangle using the given brush.
+    pub fn fill_rect(&self, area: Rectangle, brush: &impl Brush) -> io::Result<()> {
+        fill_rect(self.hdc, area, brush)
+    }
+
+    /// Draws text with a transparent background, wrapping lines at the borders of the given area.
+    pub fn draw_text(&self, area: Rectangle, text: &str, color: Rgb) -> io::Result<()> {
+        draw_text(self.hdc, area, text, color)
+    }
+
+    /// Draws a solid line with a width of 1 pixel.
+    pub fn draw_line(&self, from: Point, to: Point, color: Rgb) -> io::Result<()> {
+        draw_line(self.hdc, from, to, color)
+    }
+}
+
+impl Drop for WindowDc<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            ReleaseDC(self.window, self.hdc);
+        }
+    }
+}
+
+impl From<&WindowDc<'_>> for HDC {
+    /// Returns the underlying raw device context handle used by [`windows`].
+    fn from(value: &WindowDc<'_>) -> Self {
+        value.hdc
+    }
+}
+
+/// A color defined by its red, green and blue components.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Default, Debug)]
+pub struct Rgb {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+impl Rgb {
+    pub const fn new(red: u8, green: u8, blue: u8) -> Self {
+        Self { red, green, blue }
+    }
+}
+
+impl From<Rgb> for COLORREF {
+    fn from(value: Rgb) -> Self {
+        COLORREF(
+            u32::from(value.red) | (u32::from(value.green) << 8) | (u32::from(value.blue) << 16),
+        )
+    }
+}
+
+/// A GDI region, an area of arbitrary shape.
+///
+/// Can be used to give a window a non-rectangular shape using [`WindowHandle::set_region`].
+#[derive(Debug)]
+pub struct Region {
+    handle: HRGN,
+}
+
+impl Region {
+    pub fn from_rect(area: Rectangle) -> io::Result<Self> {
+        Self::from_maybe_invalid(unsafe { CreateRectRgnIndirect(&area) })
+    }
+
+    /// Creates an elliptic region fitting into the given area.
+    pub fn from_ellipse(area: Rectangle) -> io::Result<Self> {
+        Self::from_maybe_invalid(unsafe { CreateEllipticRgnIndirect(&area) })
+    }
+
+    /// Creates a rectangular region with rounded corners, given the width and height of the ellipse
+    /// used for the corners.
+    pub fn from_rounded_rect(
+        area: Rectangle,
+        corner_width: i32,
+        corner_height: i32,
+    ) -> io::Result<Self> {
+        Self::from_maybe_invalid(unsafe {
+            CreateRoundRectRgn(
+                area.left,
+                area.top,
+                area.right,
+                area.bottom,
+                corner_width,
+                corner_height,
+            )
+        })
+    }
+
+    /// Checks if the point is inside the region.
+    pub fn contains(&self, point: Point) -> bool {
+        unsafe { PtInRegion(self.handle, point.x, point.y) }.as_bool()
+    }
+
+    /// Returns the smallest rectangle enclosing the region.
+    pub fn get_bounds(&self) -> Rectangle {
+        let mut bounds = Rectangle::default();
+        unsafe { GetRgnBox(self.handle, &mut bounds) };
+        bounds
+    }
+
+    /// Releases ownership of the handle, which must then be deleted by someone else.
+    pub(crate) fn into_raw(self) -> HRGN {
+        let handle = self.handle;
+        std::mem::forget(self);
+        handle
+    }
+
+    fn from_maybe_invalid(handle: HRGN) -> io::Result<Self> {
+        if handle.is_invalid() {
+            Err(io::ErrorKind::Other.into())
+        } else {
+            Ok(Self { handle })
+        }
+    }
+}
+
+impl Drop for Region {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = DeleteObject(self.handle);
+        }
+    }
+}
+
+impl From<&Region> for HRGN {
+    /// Returns the underlying raw region handle used by [`windows`].
+    fn from(value: &Region) -> Self {
+        value.handle
+    }
+}
+
+fn fill_rect(hdc: HDC, area: Rectangle, brush: &impl Brush) -> io::Result<()> {
+    let brush_handle = brush.as_handle()?;
+    unsafe {
+        FillRect(hdc, &area, brush_handle)
+            .if_null_to_error_else_drop(|| io::ErrorKind::Other.into())?;
+    }
+    Ok(())
+}
+
+fn draw_text(hdc: HDC, area: Rectangle, text: &str, color: Rgb) -> io::Result<()> {
+    let mut area = area;
+    let mut wide_text: Vec<u16> = text.to_wide_string();
+    // Remove the terminating zero, the length is given by the slice.
+    wide_text.pop();
+    unsafe {
+        let previous_color = SetTextColor(hdc, COLORREF::from(color));
+        let previous_mode = SetBkMode(hdc, TRANSPARENT);
+        let result = DrawTextW(hdc, &mut wide_text, &mut area, DT_WORDBREAK | DT_NOPREFIX);
+        SetTextColor(hdc, previous_color);
+        SetBkMode(hdc, BACKGROUND_MODE(previous_mode.try_into().unwrap()));
+        result.if_null_to_error_else_drop(|| io::ErrorKind::Other.into())?;
+    }
+    Ok(())
+}
+
+fn draw_line(hdc: HDC, from: Point, to: Point, color: Rgb) -> io::Result<()> {
+    unsafe {
+        let pen = CreatePen(PS_SOLID, 1, COLORREF::from(color));
+        if pen.is_invalid() {
+            return Err(io::ErrorKind::Other.into());
+        }
+        let previous_pen = SelectObject(hdc, pen);
+        let success =
+            MoveToEx(hdc, from.x, from.y, None).as_bool() && LineTo(hdc, to.x, to.y).as_bool();
+        SelectObject(hdc, previous_pen);
+        let _ = DeleteObject(pen);
+        if !success {
+            return Err(io::ErrorKind::Other.into());
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_region() -> io::Result<()> {
+        let area = Rectangle {
+            left: 0,
+            top: 0,
+            right: 10,
+            bottom: 20,
+        };
+        let region = Region::from_rect(area)?;
+        assert!(region.contains(Point { x: 5, y: 15 }));
+        assert!(!region.contains(Point { x: 15, y: 5 }));
+        assert_eq!(region.get_bounds(), area);
+        let ellipse = Region::from_ellipse(area)?;
+        assert!(ellipse.contains(Point { x: 5, y: 10 }));
+        assert!(!ellipse.contains(Point { x: 0, y: 0 }));
+        Ok(())
+    }
+}
@@ -1,10 +1,26 @@
 //! Application resources.
 
-use std::io;
+use std::ffi::c_void;
+use std::path::Path;
+use std::{
+    io,
+    mem,
+    ptr,
+};
 
 use num_enum::IntoPrimitive;
-use windows::Win32::Foundation::HANDLE;
+use windows::core::GUID;
+use windows::Win32::Foundation::{
+    GENERIC_READ,
+    HANDLE,
+};
 use windows::Win32::Graphics::Gdi::{
+    CreateBitmap,
+    CreateDIBSection,
+    DeleteObject,
+    BITMAPINFO,
+    BITMAPINFOHEADER,
+    BI_RGB,
     COLOR_3DDKSHADOW,
     COLOR_3DLIGHT,
     COLOR_ACTIVEBORDER,
@@ -35,13 +51,27 @@ use windows::Win32::Graphics::Gdi::{
     COLOR_WINDOW,
     COLOR_WINDOWFRAME,
     COLOR_WINDOWTEXT,
+    DIB_RGB_COLORS,
+    HBITMAP,
     HBRUSH,
 };
+use windows::Win32::Graphics::Imaging::{
+    CLSID_WICImagingFactory,
+    GUID_WICPixelFormat32bppBGRA,
+    IWICBitmapDecoder,
+    IWICImagingFactory,
+    WICConvertBitmapSource,
+    WICDecodeMetadataCacheOnDemand,
+};
 use windows::Win32::UI::WindowsAndMessaging::{
+    CopyIcon,
+    CreateIconIndirect,
+    DestroyIcon,
     LoadImageW,
     GDI_IMAGE_TYPE,
     HCURSOR,
     HICON,
+    ICONINFO,
     IMAGE_CURSOR,
     IMAGE_ICON,
     LR_DEFAULTSIZE,
@@ -68,6 +98,9 @@ use windows::Win32::UI::WindowsAndMessaging::{
     OIC_WARNING,
 };
 
+use crate::com::ComInterfaceExt;
+use crate::internal::CustomAutoDrop;
+use crate::string::ZeroTerminatedWideString;
 use windows_missing::*;
 
 pub trait Icon {
@@ -93,6 +126,179 @@ impl Icon for BuiltinIcon {
     }
 }
 
+/// An icon created from image data, destroyed when dropped.
+///
+/// To use the icon for a window class or notification icon, pass a reference to it so that it stays alive
+/// as long as it is in use.
+#[derive(Eq, PartialEq, Debug)]
+pub struct OwnedIcon {
+    handle: HICON,
+}
+
+impl OwnedIcon {
+    /// Loads an icon from an image file, e.g. a PNG file.
+    ///
+    /// All formats supported by the Windows Imaging Component (WIC) can be used. The icon will have the same size
+    /// as the image.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use winapi_easy::ui::resource::{
+    ///     BuiltinColor,
+    ///     BuiltinCursor,
+    ///     OwnedIcon,
+    /// };
+    /// use winapi_easy::ui::{
+    ///     WindowClass,
+    ///     WindowClassAppearance,
+    /// };
+    /// # use winapi_easy::ui::messaging::WindowMessageListener;
+    /// # struct MyListener;
+    /// # impl WindowMessageListener for MyListener {}
+    ///
+    /// let icon = OwnedIcon::from_image_file("icon.png")?;
+    /// let class: WindowClass<MyListener> = WindowClass::register_new(
+    ///     "myclass",
+    ///     WindowClassAppearance {
+    ///         background_brush: Some(BuiltinColor::Window),
+    ///         icon: Some(&icon),
+    ///         cursor: Some(BuiltinCursor::Normal),
+    ///     },
+    ///     None,
+    /// )?;
+    ///
+    /// # Result::<(), std::io::Error>::Ok(())
+    /// ```
+    pub fn from_image_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let factory = IWICImagingFactory::new_instance()?;
+        let path = ZeroTerminatedWideString::from_os_str(path.as_ref());
+        let decoder = unsafe {
+            factory.CreateDecoderFromFilename(
+                path.as_raw_pcwstr(),
+                None,
+                GENERIC_READ,
+                WICDecodeMetadataCacheOnDemand,
+            )?
+        };
+        Self::from_decoder(&decoder)
+    }
+
+    /// Loads an icon from the contents of an image file, e.g. a PNG file embedded with [`include_bytes`].
+    ///
+    /// See also: [`Self::from_image_file`].
+    pub fn from_image_bytes(bytes: &[u8]) -> io::Result<Self> {
+        let factory = IWICImagingFactory::new_instance()?;
+        let decoder = unsafe {
+            let stream = factory.CreateStream()?;
+            stream.InitializeFromMemory(bytes)?;
+            factory.CreateDecoderFromStream(&stream, ptr::null(), WICDecodeMetadataCacheOnDemand)?
+        };
+        Self::from_decoder(&decoder)
+    }
+
+    /// Takes ownership of an icon handle, which must not be shared.
+    #[cfg(feature = "process")]
+    pub(crate) fn from_non_shared_handle(handle: HICON) -> Self {
+        Self { handle }
+    }
+
+    /// Creates an owned copy of an icon, which may be shared.
+    pub(crate) fn copy_from_handle(handle: HICON) -> io::Result<Self> {
+        let handle = unsafe { CopyIcon(handle)? };
+        Ok(Self { handle })
+    }
+
+    fn from_decoder(decoder: &IWICBitmapDecoder) -> io::Result<Self> {
+        let (width, height, pixels) = unsafe {
+            let frame = decoder.GetFrame(0)?;
+            let bitmap = WICConvertBitmapSource(&GUID_WICPixelFormat32bppBGRA, &frame)?;
+            let mut width: u32 = 0;
+            let mut height: u32 = 0;
+            bitmap.GetSize(&mut width, &mut height)?;
+            let stride = width * 4;
+            let mut pixels: Vec<u8> = vec![0; stride as usize * height as usize];
+            bitmap.CopyPixels(ptr::null(), stride, &mut pixels)?;
+            (width, height, pixels)
+        };
+        let handle = create_icon_from_bgra(
+            width.try_into().map_err(|_| io::ErrorKind::InvalidData)?,
+            height.try_into().map_err(|_| io::ErrorKind::InvalidData)?,
+            &pixels,
+        )?;
+        Ok(Self { handle })
+    }
+}
+
+impl Icon for OwnedIcon {
+    fn as_handle(&self) -> io::Result<HICON> {
+        Ok(self.handle)
+    }
+}
+
+impl Icon for &OwnedIcon {
+    fn as_handle(&self) -> io::Result<HICON> {
+        Ok(self.handle)
+    }
+}
+
+impl Drop for OwnedIcon {
+    fn drop(&mut self) {
+        let _ = unsafe { DestroyIcon(self.handle) };
+    }
+}
+
+impl ComInterfaceExt for IWICImagingFactory {
+    const CLASS_GUID: GUID = CLSID_WICImagingFactory;
+}
+
+/// Creates an icon from top-down 32 bit BGRA pixels with straight alpha.
+fn create_icon_from_bgra(width: i32, height: i32, pixels: &[u8]) -> io::Result<HICON> {
+    let bitmap_info = BITMAPINFO {
+        bmiHeader: BITMAPINFOHEADER {
+            biSize: mem::size_of::<BITMAPINFOHEADER>().try_into().unwrap(),
+            biWidth: width,
+            // Negative for a top-down bitmap
+            biHeight: -height,
+            biPlanes: 1,
+            biBitCount: 32,
+            biCompression: BI_RGB.0,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut bits: *mut c_void = ptr::null_mut();
+    let color_bitmap = CustomAutoDrop {
+        value: unsafe { CreateDIBSection(None, &bitmap_info, DIB_RGB_COLORS, &mut bits, None, 0)? },
+        drop_fn: delete_bitmap,
+    };
+    unsafe {
+        ptr::copy_nonoverlapping(pixels.as_ptr(), bits.cast::<u8>(), pixels.len());
+    }
+    // Unused for 32 bit icons with alpha channel, but still required
+    let mask_bitmap = CustomAutoDrop {
+        value: unsafe { CreateBitmap(width, height, 1, 1, None) },
+        drop_fn: delete_bitmap,
+    };
+    if mask_bitmap.value.is_invalid() {
+        return Err(io::Error::last_os_error());
+    }
+    let icon_info = ICONINFO {
+        fIcon: true.into(),
+        xHotspot: 0,
+        yHotspot: 0,
+        hbmMask: mask_bitmap.value,
+        hbmColor: color_bitmap.value,
+    };
+    // The bitmaps are copied, so they can be deleted afterwards
+    let handle = unsafe { CreateIconIndirect(&icon_info)? };
+    Ok(handle)
+}
+
+fn delete_bitmap(bitmap: &mut HBITMAP) {
+    let _ = unsafe { DeleteObject(*bitmap) };
+}
+
 pub trait Cursor {
     fn as_handle(&self) -> io::Result<HCURSOR>;
 }
@@ -142,6 +348,10 @@ pub trait Brush {
     fn as_handle(&self) -> io::Result<HBRUSH>;
 }
 
+/// A system color, usable as a [`Brush`].
+///
+/// The brush handle is the color index plus 1, which is the form expected
+/// by window class backgrounds and GDI fill functions.
 #[derive(IntoPrimitive, Copy, Clone, Eq, PartialEq, Default, Debug)]
 #[repr(i32)]
 pub enum BuiltinColor {
@@ -180,7 +390,9 @@ pub enum BuiltinColor {
 
 impl Brush for BuiltinColor {
     fn as_handle(&self) -> io::Result<HBRUSH> {
-        Ok(HBRUSH(i32::from(*self) as *mut std::ffi::c_void))
+        // System colors are passed as brush handles by adding 1 to the color index,
+        // otherwise e.g. `Window` would produce the `Menu` color and `Scrollbar` a null brush
+        Ok(HBRUSH((i32::from(*self) + 1) as *mut std::ffi::c_void))
     }
 }
 
@@ -208,3 +420,30 @@ mod windows_missing {
         PCWSTR(i as usize as *const u16)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_icon_from_png_bytes() -> io::Result<()> {
+        // A 1x1 pixel PNG image with a semi-transparent red pixel
+        const PNG_BYTES: &[u8] = &[
+            0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x48,
+            0x44, 0x52, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x06, 0x00, 0x00,
+            0x00, 0x1f, 0x15, 0xc4, 0x89, 0x00, 0x00, 0x00, 0x0d, 0x49, 0x44, 0x41, 0x54, 0x78,
+            0x9c, 0x63, 0xf8, 0xcf, 0xc0, 0xd0, 0x00, 0x00, 0x04, 0x81, 0x01, 0x80, 0x2c, 0x55,
+            0xce, 0xb0, 0x00, 0x00, 0x00, 0x00, 0x49, 0x45, 0x4e, 0x44, 0xae, 0x42, 0x60, 0x82,
+        ];
+        let icon = OwnedIcon::from_image_bytes(PNG_BYTES)?;
+        assert!(!icon.as_handle()?.is_invalid());
+        Ok(())
+    }
+
+    #[test]
+    fn builtin_color_brush_handle() -> io::Result<()> {
+        let brush = BuiltinColor::Window.as_handle()?;
+        assert_eq!(brush.0 as i32, COLOR_WINDOW.0 + 1);
+        Ok(())
+    }
+}